@@ -0,0 +1,276 @@
+use crate::{
+    gate::{AndGate, NotGate, OrGate, XorGate},
+    multiplexer::TwoBitMultiplexer,
+    shifter::BarrelShifter,
+    subtractor::{AddSubMode, NBitAdderSubtractor},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AluOp {
+    Add,
+    Sub,
+    And,
+    Or,
+    Xor,
+    Not,
+    Shl,
+    Shr,
+    Sar,
+    Rol,
+    Ror,
+    Slt,
+    Sltu,
+    Eq,
+    PassA,
+    PassB,
+}
+impl AluOp {
+    pub fn opcode(self) -> [bool; 4] {
+        let code = self as usize;
+        [code & 1 == 1, code & 2 == 2, code & 4 == 4, code & 8 == 8]
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct AluFlags {
+    pub zero: bool,
+    pub carry: bool,
+    pub negative: bool,
+    pub overflow: bool,
+}
+
+pub struct Alu<const N: usize> {
+    a: [bool; N],
+    b: [bool; N],
+    op: [bool; 4],
+}
+impl<const N: usize> Alu<N> {
+    pub fn new(a: [bool; N], b: [bool; N], op: [bool; 4]) -> Self {
+        Self { a, b, op }
+    }
+    pub fn set_a(&mut self, a: [bool; N]) {
+        self.a = a;
+    }
+    pub fn set_b(&mut self, b: [bool; N]) {
+        self.b = b;
+    }
+    pub fn set_op(&mut self, op: [bool; 4]) {
+        self.op = op;
+    }
+    pub fn output(&self) -> ([bool; N], AluFlags) {
+        let mut add_inputs = [true; 16];
+        add_inputs[AluOp::Add as usize] = false;
+        let sub = self.select(add_inputs);
+        let mode = if sub {
+            AddSubMode::Sub
+        } else {
+            AddSubMode::Add
+        };
+        let (sum, carry) = NBitAdderSubtractor::new(self.a, self.b, mode).output();
+        let shift = |right, arithmetic, rotate| {
+            BarrelShifter::new(self.a, self.b, right, arithmetic, rotate).output()
+        };
+        let shl = shift(false, false, false);
+        let shr = shift(true, false, false);
+        let sar = shift(true, true, false);
+        let rol = shift(false, false, true);
+        let ror = shift(true, false, true);
+
+        let (overflow, slt) = match N {
+            0 => (false, false),
+            _ => {
+                let b = XorGate::new(self.b[N - 1], sub);
+                let overflow = Self::overflow(self.a[N - 1], b.output(), sum[N - 1]);
+                let slt = XorGate::new(sum[N - 1], overflow);
+                (overflow, slt.output())
+            }
+        };
+        let sltu = NotGate::new(carry).output();
+        let eq = Self::is_zero(&sum);
+
+        let mut result = [false; N];
+        (0..N).for_each(|i| {
+            let (a, b) = (self.a[i], self.b[i]);
+            let first = i == 0;
+            result[i] = self.select([
+                sum[i],
+                sum[i],
+                AndGate::new(a, b).output(),
+                OrGate::new(a, b).output(),
+                XorGate::new(a, b).output(),
+                NotGate::new(a).output(),
+                shl[i],
+                shr[i],
+                sar[i],
+                rol[i],
+                ror[i],
+                first && slt,
+                first && sltu,
+                first && eq,
+                a,
+                b,
+            ]);
+        });
+
+        let mut carry_inputs = [false; 16];
+        let mut overflow_inputs = [false; 16];
+        [AluOp::Add, AluOp::Sub, AluOp::Slt, AluOp::Sltu, AluOp::Eq]
+            .into_iter()
+            .for_each(|op| {
+                carry_inputs[op as usize] = carry;
+                overflow_inputs[op as usize] = overflow;
+            });
+
+        let flags = AluFlags {
+            zero: Self::is_zero(&result),
+            carry: self.select(carry_inputs),
+            negative: N > 0 && result[N - 1],
+            overflow: self.select(overflow_inputs),
+        };
+        (result, flags)
+    }
+    fn select(&self, inputs: [bool; 16]) -> bool {
+        let low = (self.op[0], self.op[1]);
+        let high = (self.op[2], self.op[3]);
+        let mux = |i: usize| {
+            TwoBitMultiplexer::new(inputs[i], inputs[i + 1], inputs[i + 2], inputs[i + 3], low)
+                .output()
+        };
+        let mux = TwoBitMultiplexer::new(mux(0), mux(4), mux(8), mux(12), high);
+        mux.output()
+    }
+    fn overflow(a: bool, b: bool, result: bool) -> bool {
+        let same_sign = NotGate::new(XorGate::new(a, b).output());
+        let sign_changed = XorGate::new(a, result);
+        let and = AndGate::new(same_sign.output(), sign_changed.output());
+        and.output()
+    }
+    fn is_zero(bits: &[bool]) -> bool {
+        let any = bits
+            .iter()
+            .fold(false, |acc, &bit| OrGate::new(acc, bit).output());
+        NotGate::new(any).output()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bits::{from_u64, to_u64};
+
+    #[test]
+    fn test_alu() {
+        let mut alu = Alu::new(
+            [true, true, false, false],
+            [true, false, true, false],
+            AluOp::Add.opcode(),
+        );
+        assert_eq!(alu.output().0, [false, false, false, true]);
+        alu.set_op(AluOp::And.opcode());
+        assert_eq!(alu.output().0, [true, false, false, false]);
+        alu.set_op(AluOp::Or.opcode());
+        assert_eq!(alu.output().0, [true, true, true, false]);
+        alu.set_op(AluOp::Xor.opcode());
+        assert_eq!(alu.output().0, [false, true, true, false]);
+        alu.set_op(AluOp::Not.opcode());
+        assert_eq!(alu.output().0, [false, false, true, true]);
+        alu.set_b([true, true, false, false]);
+        alu.set_op(AluOp::Eq.opcode());
+        assert_eq!(alu.output().0, [true, false, false, false]);
+        alu.set_a([false; 4]);
+        alu.set_op(AluOp::Sub.opcode());
+        assert_eq!(
+            alu.output(),
+            (
+                [true, false, true, true],
+                AluFlags {
+                    zero: false,
+                    carry: false,
+                    negative: true,
+                    overflow: false,
+                }
+            )
+        );
+    }
+
+    #[test]
+    fn test_alu_against_u8() {
+        let values = || (0..=255u8).step_by(15).chain([1, 0x7f, 0x80, 0xff]);
+        for a in values() {
+            for b in values() {
+                let (sa, sb) = (a as i8, b as i8);
+                let cases = [
+                    (
+                        AluOp::Add,
+                        a.wrapping_add(b),
+                        a.checked_add(b).is_none(),
+                        sa.checked_add(sb).is_none(),
+                    ),
+                    (
+                        AluOp::Sub,
+                        a.wrapping_sub(b),
+                        a >= b,
+                        sa.checked_sub(sb).is_none(),
+                    ),
+                    (AluOp::And, a & b, false, false),
+                    (AluOp::Or, a | b, false, false),
+                    (AluOp::Xor, a ^ b, false, false),
+                    (AluOp::Not, !a, false, false),
+                    (
+                        AluOp::Shl,
+                        a.checked_shl(b as u32).unwrap_or(0),
+                        false,
+                        false,
+                    ),
+                    (
+                        AluOp::Shr,
+                        a.checked_shr(b as u32).unwrap_or(0),
+                        false,
+                        false,
+                    ),
+                    (AluOp::Sar, (sa >> (b as u32).min(7)) as u8, false, false),
+                    (AluOp::Rol, a.rotate_left(b as u32), false, false),
+                    (AluOp::Ror, a.rotate_right(b as u32), false, false),
+                    (
+                        AluOp::Slt,
+                        (sa < sb) as u8,
+                        a >= b,
+                        sa.checked_sub(sb).is_none(),
+                    ),
+                    (
+                        AluOp::Sltu,
+                        (a < b) as u8,
+                        a >= b,
+                        sa.checked_sub(sb).is_none(),
+                    ),
+                    (
+                        AluOp::Eq,
+                        (a == b) as u8,
+                        a >= b,
+                        sa.checked_sub(sb).is_none(),
+                    ),
+                    (AluOp::PassA, a, false, false),
+                    (AluOp::PassB, b, false, false),
+                ];
+                for (op, expected, carry, overflow) in cases {
+                    let alu = Alu::new(from_u64::<8>(a as u64), from_u64(b as u64), op.opcode());
+                    let (result, flags) = alu.output();
+                    assert_eq!(to_u64(&result), expected as u64, "{:?} {} {}", op, a, b);
+                    assert_eq!(
+                        flags,
+                        AluFlags {
+                            zero: expected == 0,
+                            carry,
+                            negative: expected & 0x80 != 0,
+                            overflow,
+                        },
+                        "{:?} {} {}",
+                        op,
+                        a,
+                        b
+                    );
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,196 @@
+use crate::{
+    adder::NBitFullAdder,
+    gate::{AndGate, OrGate},
+    subtractor::NBitFullSubtractor,
+};
+
+pub struct BcdDigitAdder {
+    a: [bool; 4],
+    b: [bool; 4],
+    carry_in: bool,
+}
+impl BcdDigitAdder {
+    pub fn new(a: [bool; 4], b: [bool; 4], carry_in: bool) -> Self {
+        Self { a, b, carry_in }
+    }
+    pub fn set_a(&mut self, a: [bool; 4]) {
+        self.a = a;
+    }
+    pub fn set_b(&mut self, b: [bool; 4]) {
+        self.b = b;
+    }
+    pub fn set_carry_in(&mut self, carry_in: bool) {
+        self.carry_in = carry_in;
+    }
+    pub fn output(&self) -> ([bool; 4], bool) {
+        let (sum, carry1) = NBitFullAdder::new(self.a, self.b).output();
+        let (sum, carry2) = NBitFullAdder::new(sum, [self.carry_in, false, false, false]).output();
+        let binary_carry = OrGate::new(carry1, carry2);
+        // A sum above 9 needs +6 to skip the six unused codes and carry to the next digit.
+        let and1 = AndGate::new(sum[3], sum[2]);
+        let and2 = AndGate::new(sum[3], sum[1]);
+        let or1 = OrGate::new(and1.output(), and2.output());
+        let or2 = OrGate::new(binary_carry.output(), or1.output());
+        let correction = or2.output();
+        let (sum, _) = NBitFullAdder::new(sum, [false, correction, correction, false]).output();
+        (sum, correction)
+    }
+}
+
+pub struct NDigitBcdAdder<const D: usize> {
+    a: [[bool; 4]; D],
+    b: [[bool; 4]; D],
+}
+impl<const D: usize> NDigitBcdAdder<D> {
+    pub fn new(a: [[bool; 4]; D], b: [[bool; 4]; D]) -> Self {
+        Self { a, b }
+    }
+    pub fn set_a(&mut self, a: [[bool; 4]; D]) {
+        self.a = a;
+    }
+    pub fn set_b(&mut self, b: [[bool; 4]; D]) {
+        self.b = b;
+    }
+    pub fn output(&self) -> ([[bool; 4]; D], bool) {
+        let mut carry = false;
+        let mut result = [[false; 4]; D];
+        (0..D).for_each(|i| {
+            let digit_adder = BcdDigitAdder::new(self.a[i], self.b[i], carry);
+            let digit_adder_output = digit_adder.output();
+            result[i] = digit_adder_output.0;
+            carry = digit_adder_output.1;
+        });
+        (result, carry)
+    }
+}
+
+pub struct BinaryToBcd<const N: usize, const D: usize> {
+    a: [bool; N],
+}
+impl<const N: usize, const D: usize> BinaryToBcd<N, D> {
+    pub fn new(a: [bool; N]) -> Self {
+        Self { a }
+    }
+    pub fn set_a(&mut self, a: [bool; N]) {
+        self.a = a;
+    }
+    pub fn output(&self) -> [[bool; 4]; D] {
+        // Double dabble: before each left shift, add 3 to digits of 5 or more.
+        let mut digits = [[false; 4]; D];
+        (0..N).rev().for_each(|i| {
+            digits.iter_mut().for_each(|digit| {
+                let or = OrGate::new(digit[1], digit[0]);
+                let and = AndGate::new(digit[2], or.output());
+                let at_least_five = OrGate::new(digit[3], and.output());
+                let k = at_least_five.output();
+                *digit = NBitFullAdder::new(*digit, [k, k, false, false]).output().0;
+            });
+            let mut shift_in = self.a[i];
+            digits.iter_mut().for_each(|digit| {
+                let shift_out = digit[3];
+                *digit = [shift_in, digit[0], digit[1], digit[2]];
+                shift_in = shift_out;
+            });
+        });
+        digits
+    }
+}
+
+pub struct BcdToBinary<const D: usize, const N: usize> {
+    a: [[bool; 4]; D],
+}
+impl<const D: usize, const N: usize> BcdToBinary<D, N> {
+    pub fn new(a: [[bool; 4]; D]) -> Self {
+        Self { a }
+    }
+    pub fn set_a(&mut self, a: [[bool; 4]; D]) {
+        self.a = a;
+    }
+    pub fn output(&self) -> [bool; N] {
+        // Reverse double dabble: after each right shift, subtract 3 from digits of 8 or more.
+        let mut digits = self.a;
+        let mut result = [false; N];
+        (0..N).for_each(|_| {
+            let mut shift_in = false;
+            digits.iter_mut().rev().for_each(|digit| {
+                let shift_out = digit[0];
+                *digit = [digit[1], digit[2], digit[3], shift_in];
+                shift_in = shift_out;
+            });
+            result.rotate_left(1);
+            if N > 0 {
+                result[N - 1] = shift_in;
+            }
+            digits.iter_mut().for_each(|digit| {
+                let k = digit[3];
+                *digit = NBitFullSubtractor::new(*digit, [k, k, false, false])
+                    .output()
+                    .0;
+            });
+        });
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bits::{from_u64, to_u64};
+
+    fn to_bcd<const D: usize>(value: u64) -> [[bool; 4]; D] {
+        let mut digits = [[false; 4]; D];
+        (0..D).fold(value, |value, i| {
+            digits[i] = from_u64(value % 10);
+            value / 10
+        });
+        digits
+    }
+
+    fn from_bcd(digits: &[[bool; 4]]) -> u64 {
+        digits
+            .iter()
+            .rev()
+            .fold(0, |acc, digit| acc * 10 + to_u64(digit))
+    }
+
+    #[test]
+    fn test_bcd_digit_adder() {
+        for a in 0..10 {
+            for b in 0..10 {
+                for carry_in in [false, true] {
+                    let adder = BcdDigitAdder::new(from_u64(a), from_u64(b), carry_in);
+                    let (sum, carry) = adder.output();
+                    let expected = a + b + carry_in as u64;
+                    assert_eq!((to_u64(&sum), carry), (expected % 10, expected >= 10));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_n_digit_bcd_adder() {
+        let adder = NDigitBcdAdder::new(to_bcd::<3>(456), to_bcd(789));
+        let (sum, carry) = adder.output();
+        assert_eq!((from_bcd(&sum), carry), (245, true));
+
+        let adder = NDigitBcdAdder::new(to_bcd::<3>(123), to_bcd(76));
+        let (sum, carry) = adder.output();
+        assert_eq!((from_bcd(&sum), carry), (199, false));
+    }
+
+    #[test]
+    fn test_binary_to_bcd() {
+        for value in 0..=255 {
+            let converter = BinaryToBcd::<8, 3>::new(from_u64(value));
+            assert_eq!(from_bcd(&converter.output()), value);
+        }
+    }
+
+    #[test]
+    fn test_bcd_to_binary() {
+        for value in 0..=255 {
+            let converter = BcdToBinary::<3, 8>::new(to_bcd(value));
+            assert_eq!(to_u64(&converter.output()), value);
+        }
+    }
+}
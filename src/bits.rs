@@ -0,0 +1,27 @@
+pub fn from_u64<const N: usize>(value: u64) -> [bool; N] {
+    let mut bits = [false; N];
+    (0..N.min(64)).for_each(|i| {
+        bits[i] = (value >> i) & 1 == 1;
+    });
+    bits
+}
+
+pub fn to_u64(bits: &[bool]) -> u64 {
+    bits.iter()
+        .take(64)
+        .enumerate()
+        .fold(0, |acc, (i, &bit)| acc | ((bit as u64) << i))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bits_conversion() {
+        assert_eq!(from_u64::<4>(0b0110), [false, true, true, false]);
+        assert_eq!(from_u64::<2>(0b111), [true, true]);
+        assert_eq!(to_u64(&[true, false, true]), 5);
+        assert_eq!(to_u64(&from_u64::<8>(0xa5)), 0xa5);
+    }
+}
@@ -0,0 +1,211 @@
+use std::{error::Error, fmt};
+
+use crate::gate::{AndGate, NotGate};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Logic {
+    Low,
+    High,
+    HighZ,
+    Conflict,
+    /// Driven, but to a value the simulation cannot know, e.g. a metastable flip-flop.
+    Unknown,
+}
+impl Logic {
+    pub fn from_bool(value: bool) -> Logic {
+        if value {
+            Logic::High
+        } else {
+            Logic::Low
+        }
+    }
+    pub fn to_bool(self) -> Option<bool> {
+        match self {
+            Logic::Low => Some(false),
+            Logic::High => Some(true),
+            Logic::HighZ | Logic::Conflict | Logic::Unknown => None,
+        }
+    }
+    pub fn resolve(self, other: Logic) -> Logic {
+        match (self, other) {
+            (Logic::HighZ, other) | (other, Logic::HighZ) => other,
+            (a, b) if a == b => a,
+            (Logic::Unknown, Logic::Low | Logic::High)
+            | (Logic::Low | Logic::High, Logic::Unknown) => Logic::Unknown,
+            _ => Logic::Conflict,
+        }
+    }
+}
+
+pub struct TriStateBuffer {
+    a: bool,
+    enable: bool,
+}
+impl TriStateBuffer {
+    pub fn new(a: bool, enable: bool) -> TriStateBuffer {
+        TriStateBuffer { a, enable }
+    }
+    pub fn set_a(&mut self, a: bool) {
+        self.a = a;
+    }
+    pub fn set_enable(&mut self, enable: bool) {
+        self.enable = enable;
+    }
+    pub fn a(&self) -> bool {
+        self.a
+    }
+    pub fn enable(&self) -> bool {
+        self.enable
+    }
+    pub fn output(&self) -> Logic {
+        // Pull-up and pull-down transistors: at most one conducts, neither when disabled.
+        let not_a = NotGate::new(self.a);
+        let pull_up = AndGate::new(self.a, self.enable);
+        let pull_down = AndGate::new(not_a.output(), self.enable);
+        match (pull_up.output(), pull_down.output()) {
+            (true, _) => Logic::High,
+            (_, true) => Logic::Low,
+            _ => Logic::HighZ,
+        }
+    }
+}
+
+pub struct NBitTriStateBuffer<const N: usize> {
+    a: [bool; N],
+    enable: bool,
+}
+impl<const N: usize> NBitTriStateBuffer<N> {
+    pub fn new(a: [bool; N], enable: bool) -> Self {
+        Self { a, enable }
+    }
+    pub fn set_a(&mut self, a: [bool; N]) {
+        self.a = a;
+    }
+    pub fn set_enable(&mut self, enable: bool) {
+        self.enable = enable;
+    }
+    pub fn a(&self) -> [bool; N] {
+        self.a
+    }
+    pub fn enable(&self) -> bool {
+        self.enable
+    }
+    pub fn output(&self) -> [Logic; N] {
+        let mut output = [Logic::HighZ; N];
+        (0..N).for_each(|i| {
+            let buffer = TriStateBuffer::new(self.a[i], self.enable);
+            output[i] = buffer.output();
+        });
+        output
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BusError {
+    Floating(usize),
+    Contention(usize),
+    Unknown(usize),
+}
+impl fmt::Display for BusError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BusError::Floating(bit) => write!(f, "bus bit {} is floating", bit),
+            BusError::Contention(bit) => write!(f, "bus bit {} has conflicting drivers", bit),
+            BusError::Unknown(bit) => write!(f, "bus bit {} is driven to an unknown value", bit),
+        }
+    }
+}
+impl Error for BusError {}
+
+pub struct Bus<const N: usize> {
+    lines: [Logic; N],
+}
+impl<const N: usize> Bus<N> {
+    pub fn new() -> Self {
+        Self {
+            lines: [Logic::HighZ; N],
+        }
+    }
+    pub fn release(&mut self) {
+        self.lines = [Logic::HighZ; N];
+    }
+    pub fn drive(&mut self, value: [Logic; N]) {
+        (0..N).for_each(|i| {
+            self.lines[i] = self.lines[i].resolve(value[i]);
+        });
+    }
+    pub fn output(&self) -> [Logic; N] {
+        self.lines
+    }
+    pub fn read(&self) -> Result<[bool; N], BusError> {
+        let mut value = [false; N];
+        for (i, line) in self.lines.iter().enumerate() {
+            value[i] = match line {
+                Logic::Low => false,
+                Logic::High => true,
+                Logic::HighZ => return Err(BusError::Floating(i)),
+                Logic::Conflict => return Err(BusError::Contention(i)),
+                Logic::Unknown => return Err(BusError::Unknown(i)),
+            };
+        }
+        Ok(value)
+    }
+}
+impl<const N: usize> Default for Bus<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tri_state_buffer() {
+        let mut buffer = TriStateBuffer::new(true, false);
+        assert_eq!(buffer.output(), Logic::HighZ);
+        buffer.set_enable(true);
+        assert_eq!(buffer.output(), Logic::High);
+        buffer.set_a(false);
+        assert_eq!(buffer.output(), Logic::Low);
+    }
+
+    #[test]
+    fn test_logic_resolve() {
+        assert_eq!(Logic::HighZ.resolve(Logic::Unknown), Logic::Unknown);
+        assert_eq!(Logic::High.resolve(Logic::Unknown), Logic::Unknown);
+        assert_eq!(Logic::High.resolve(Logic::High), Logic::High);
+        assert_eq!(Logic::High.resolve(Logic::Low), Logic::Conflict);
+        assert_eq!(Logic::Conflict.resolve(Logic::Unknown), Logic::Conflict);
+
+        let mut bus = Bus::<1>::new();
+        bus.drive([Logic::Unknown]);
+        assert_eq!(bus.read(), Err(BusError::Unknown(0)));
+    }
+
+    #[test]
+    fn test_bus() {
+        let mut bus = Bus::<2>::new();
+        assert_eq!(bus.read(), Err(BusError::Floating(0)));
+
+        let mut buffer1 = NBitTriStateBuffer::new([true, false], true);
+        let mut buffer2 = NBitTriStateBuffer::new([true, true], false);
+        bus.drive(buffer1.output());
+        bus.drive(buffer2.output());
+        assert_eq!(bus.read(), Ok([true, false]));
+
+        bus.release();
+        buffer2.set_enable(true);
+        bus.drive(buffer1.output());
+        bus.drive(buffer2.output());
+        assert_eq!(bus.output(), [Logic::High, Logic::Conflict]);
+        assert_eq!(bus.read(), Err(BusError::Contention(1)));
+
+        bus.release();
+        buffer1.set_enable(false);
+        bus.drive(buffer1.output());
+        bus.drive(buffer2.output());
+        assert_eq!(bus.read(), Ok([true, true]));
+    }
+}
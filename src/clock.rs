@@ -0,0 +1,230 @@
+use std::{thread, time::Duration};
+
+/// A periodic clock in simulated time units: high for `high` units starting at `phase`, then
+/// low for the rest of each `period`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Clock {
+    period: u64,
+    high: u64,
+    phase: u64,
+}
+impl Clock {
+    pub fn new(period: u64) -> Clock {
+        assert!(period >= 2, "clock period must be at least 2");
+        Clock::with_duty_cycle(period, period / 2)
+    }
+    pub fn with_duty_cycle(period: u64, high: u64) -> Clock {
+        assert!(
+            0 < high && high < period,
+            "clock high time must be between 0 and the period"
+        );
+        Clock {
+            period,
+            high,
+            phase: 0,
+        }
+    }
+    pub fn with_phase(self, phase: u64) -> Clock {
+        Clock { phase, ..self }
+    }
+    /// Non-overlapping clocks, each high for its own 1/count slice of the period. A single
+    /// phase is the plain `Clock::new(period)`, so it needs a period of at least 2.
+    pub fn phases(period: u64, count: u64) -> Vec<Clock> {
+        assert!(
+            0 < count && count <= period,
+            "clock phases require 0 < count <= period"
+        );
+        if count == 1 {
+            return vec![Clock::new(period)];
+        }
+        let slice = period / count;
+        (0..count)
+            .map(|i| Clock::with_duty_cycle(period, slice).with_phase(i * slice))
+            .collect()
+    }
+    pub fn period(&self) -> u64 {
+        self.period
+    }
+    pub fn high(&self) -> u64 {
+        self.high
+    }
+    pub fn phase(&self) -> u64 {
+        self.phase
+    }
+    pub fn level_at(&self, time: u64) -> bool {
+        time >= self.phase && (time - self.phase) % self.period < self.high
+    }
+    /// The first edge at or after `time`, as (time, level after the edge).
+    pub fn edge_at_or_after(&self, time: u64) -> (u64, bool) {
+        if time < self.phase {
+            return (self.phase, true);
+        }
+        let offset = (time - self.phase) % self.period;
+        let start = time - offset;
+        match offset {
+            0 => (time, true),
+            offset if offset <= self.high => (start + self.high, false),
+            _ => (start + self.period, true),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ClockEvent {
+    pub clock: usize,
+    pub time: u64,
+    pub level: bool,
+}
+
+/// Steps simulated time from one clock edge to the next. Edges at the same time are delivered
+/// in the order the clocks were added, so runs are fully deterministic.
+pub struct Scheduler {
+    time: u64,
+    clocks: Vec<(Clock, u64)>,
+    pacing: Option<Duration>,
+}
+impl Scheduler {
+    pub fn new() -> Scheduler {
+        Scheduler {
+            time: 0,
+            clocks: Vec::new(),
+            pacing: None,
+        }
+    }
+    pub fn add_clock(&mut self, clock: Clock) -> usize {
+        let (next, _) = clock.edge_at_or_after(self.time);
+        self.clocks.push((clock, next));
+        self.clocks.len() - 1
+    }
+    /// Sleeps for `pacing` per simulated time unit so demos can be watched in real time.
+    pub fn set_pacing(&mut self, pacing: Option<Duration>) {
+        self.pacing = pacing;
+    }
+    pub fn time(&self) -> u64 {
+        self.time
+    }
+    pub fn level(&self, clock: usize) -> bool {
+        self.clocks[clock].0.level_at(self.time)
+    }
+    pub fn peek(&self) -> Option<ClockEvent> {
+        self.clocks
+            .iter()
+            .enumerate()
+            .min_by_key(|(_, (_, next))| *next)
+            .map(|(i, (clock, next))| ClockEvent {
+                clock: i,
+                time: *next,
+                level: clock.edge_at_or_after(*next).1,
+            })
+    }
+    pub fn step(&mut self) -> Option<ClockEvent> {
+        let event = self.peek()?;
+        if let Some(pacing) = self.pacing {
+            let elapsed = event.time - self.time;
+            thread::sleep(pacing.saturating_mul(u32::try_from(elapsed).unwrap_or(u32::MAX)));
+        }
+        self.time = event.time;
+        let (clock, next) = &mut self.clocks[event.clock];
+        *next = clock.edge_at_or_after(event.time + 1).0;
+        Some(event)
+    }
+    /// Delivers every edge up to and including `end`, then moves time to `end`.
+    pub fn run_until(&mut self, end: u64, mut f: impl FnMut(ClockEvent)) {
+        while let Some(event) = self.peek().filter(|event| event.time <= end) {
+            self.step();
+            f(event);
+        }
+        self.time = self.time.max(end);
+    }
+}
+impl Default for Scheduler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::flip_flop::TFlipFlop;
+
+    #[test]
+    fn test_clock() {
+        let clock = Clock::with_duty_cycle(10, 3).with_phase(4);
+        let levels = (0..16).map(|t| clock.level_at(t)).collect::<Vec<_>>();
+        assert_eq!(
+            levels,
+            [
+                false, false, false, false, true, true, true, false, false, false, false, false,
+                false, false, true, true
+            ]
+        );
+        assert_eq!(clock.edge_at_or_after(0), (4, true));
+        assert_eq!(clock.edge_at_or_after(4), (4, true));
+        assert_eq!(clock.edge_at_or_after(5), (7, false));
+        assert_eq!(clock.edge_at_or_after(8), (14, true));
+
+        let phases = Clock::phases(12, 3);
+        assert_eq!(
+            phases
+                .iter()
+                .map(|c| (c.high(), c.phase()))
+                .collect::<Vec<_>>(),
+            [(4, 0), (4, 4), (4, 8)]
+        );
+        (0..24).for_each(|t| {
+            assert_eq!(phases.iter().filter(|c| c.level_at(t)).count(), 1);
+        });
+        assert_eq!(Clock::phases(10, 1), [Clock::new(10)]);
+        assert_eq!(
+            Clock::phases(3, 3)[2],
+            Clock::with_duty_cycle(3, 1).with_phase(2)
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "clock period must be at least 2")]
+    fn test_clock_single_phase_too_short() {
+        Clock::phases(1, 1);
+    }
+
+    #[test]
+    fn test_scheduler() {
+        let mut scheduler = Scheduler::new();
+        let fast = scheduler.add_clock(Clock::new(4));
+        let slow = scheduler.add_clock(Clock::new(8).with_phase(2));
+        let mut events = vec![];
+        scheduler.run_until(10, |event| {
+            events.push((event.clock, event.time, event.level))
+        });
+        assert_eq!(
+            events,
+            [
+                (fast, 0, true),
+                (fast, 2, false),
+                (slow, 2, true),
+                (fast, 4, true),
+                (fast, 6, false),
+                (slow, 6, false),
+                (fast, 8, true),
+                (fast, 10, false),
+                (slow, 10, true),
+            ]
+        );
+        assert_eq!(scheduler.time(), 10);
+        assert!(scheduler.level(slow));
+
+        let mut t_flip_flop = TFlipFlop::new();
+        t_flip_flop.set_t(true);
+        let mut scheduler = Scheduler::new();
+        scheduler.add_clock(Clock::new(10));
+        let mut outputs = vec![];
+        scheduler.run_until(45, |event| {
+            t_flip_flop.set_clk(event.level);
+            if event.level {
+                outputs.push(t_flip_flop.output());
+            }
+        });
+        assert_eq!(outputs, [true, false, true, false, true]);
+    }
+}
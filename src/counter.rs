@@ -0,0 +1,279 @@
+use crate::{
+    flip_flop::ResettableDFlipFlop,
+    gate::{AndGate, NotGate, OrGate, XorGate},
+    multiplexer::{BusMux, OneBitMultiplexer},
+    register::Register,
+    subtractor::{AddSubMode, NBitAdderSubtractor},
+};
+
+pub struct RippleCounter<const N: usize> {
+    dff: [ResettableDFlipFlop; N],
+    data: [bool; N],
+    clk: bool,
+    up: bool,
+    enable: bool,
+    load: bool,
+    reset: bool,
+}
+impl<const N: usize> RippleCounter<N> {
+    pub fn new() -> Self {
+        let mut counter = Self {
+            dff: std::array::from_fn(|_| ResettableDFlipFlop::new()),
+            data: [false; N],
+            clk: false,
+            up: true,
+            enable: true,
+            load: false,
+            reset: false,
+        };
+        counter.update();
+        counter
+    }
+    pub fn set_clk(&mut self, clk: bool) {
+        self.clk = clk;
+        self.update();
+    }
+    /// As in hardware, flipping the direction swaps the stage clocks between Q and /Q and may
+    /// clock stages, so switch to down at zero, to up at all ones, or while `load` is held.
+    pub fn set_up(&mut self, up: bool) {
+        self.up = up;
+        self.update();
+    }
+    pub fn set_enable(&mut self, enable: bool) {
+        self.enable = enable;
+        self.update();
+    }
+    /// Loading is asynchronous, as in a 74HC193: while `load` is high every stage is forced
+    /// to its data bit through preset/clear, regardless of the clock.
+    pub fn set_load(&mut self, load: bool) {
+        self.load = load;
+        self.update();
+    }
+    pub fn set_data(&mut self, data: [bool; N]) {
+        self.data = data;
+        self.update();
+    }
+    pub fn set_reset(&mut self, reset: bool) {
+        self.reset = reset;
+        self.update();
+    }
+    pub fn output(&self) -> [bool; N] {
+        std::array::from_fn(|i| self.dff[i].output())
+    }
+    pub fn terminal_count(&self) -> bool {
+        let and = AndGate::new(terminal_count(self.output(), self.up), self.enable);
+        and.output()
+    }
+    fn update(&mut self) {
+        // Each stage toggles on its own clock, which is driven by the stage below: /Q when
+        // counting up, Q when counting down.
+        let mut clk = self.clk;
+        (0..N).for_each(|i| {
+            // Reset wins over load because the flip-flops give clear priority over preset.
+            let not_data = NotGate::new(self.data[i]);
+            let load_one = AndGate::new(self.load, self.data[i]);
+            let load_zero = AndGate::new(self.load, not_data.output());
+            let clear = OrGate::new(self.reset, load_zero.output());
+            self.dff[i].set_clear(clear.output());
+            self.dff[i].set_preset(load_one.output());
+            self.dff[i].set_clk(clk);
+            let q = self.dff[i].output();
+            let toggle = if i == 0 { self.enable } else { true };
+            let xor = XorGate::new(q, toggle);
+            self.dff[i].set_d(xor.output());
+            let next_clk = XorGate::new(q, self.up);
+            clk = next_clk.output();
+        });
+    }
+}
+impl<const N: usize> Default for RippleCounter<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+pub struct SynchronousCounter<const N: usize> {
+    register: Register<N>,
+    data: [bool; N],
+    up: bool,
+    load: bool,
+    enable: bool,
+}
+impl<const N: usize> SynchronousCounter<N> {
+    pub fn new() -> Self {
+        let mut counter = Self {
+            register: Register::new(),
+            data: [false; N],
+            up: true,
+            load: false,
+            enable: true,
+        };
+        counter.update();
+        counter
+    }
+    pub fn set_clk(&mut self, clk: bool) {
+        self.register.set_clk(clk);
+        self.update();
+    }
+    pub fn set_up(&mut self, up: bool) {
+        self.up = up;
+        self.update();
+    }
+    pub fn set_enable(&mut self, enable: bool) {
+        self.enable = enable;
+        self.update();
+    }
+    pub fn set_load(&mut self, load: bool) {
+        self.load = load;
+        self.update();
+    }
+    pub fn set_data(&mut self, data: [bool; N]) {
+        self.data = data;
+        self.update();
+    }
+    pub fn set_reset(&mut self, reset: bool) {
+        self.register.set_reset(reset);
+        self.update();
+    }
+    pub fn output(&self) -> [bool; N] {
+        self.register.output()
+    }
+    pub fn terminal_count(&self) -> bool {
+        let and = AndGate::new(terminal_count(self.output(), self.up), self.enable);
+        and.output()
+    }
+    fn update(&mut self) {
+        let q = self.register.output();
+        let increment = NBitAdderSubtractor::new(q, [false; N], AddSubMode::Increment);
+        let decrement = NBitAdderSubtractor::new(q, [false; N], AddSubMode::Decrement);
+        let count = BusMux::new([decrement.output().0, increment.output().0], [self.up]);
+        let next = BusMux::new([count.output(), self.data], [self.load]);
+        let enable = OrGate::new(self.enable, self.load);
+        self.register.set_enable(enable.output());
+        self.register.set_d(next.output());
+    }
+}
+impl<const N: usize> Default for SynchronousCounter<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// All ones when counting up, all zeros when counting down.
+fn terminal_count(q: impl IntoIterator<Item = bool>, up: bool) -> bool {
+    let (all_ones, all_zeros) = q.into_iter().fold((true, true), |(ones, zeros), bit| {
+        let not = NotGate::new(bit);
+        let ones = AndGate::new(ones, bit);
+        let zeros = AndGate::new(zeros, not.output());
+        (ones.output(), zeros.output())
+    });
+    let mux = OneBitMultiplexer::new(all_zeros, all_ones, up);
+    mux.output()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bits::to_u64;
+
+    #[test]
+    fn test_ripple_counter() {
+        let mut counter = RippleCounter::<4>::new();
+        for i in 1..=16 {
+            counter.set_clk(true);
+            counter.set_clk(false);
+            assert_eq!(to_u64(&counter.output()), i % 16);
+            assert_eq!(counter.terminal_count(), i % 16 == 15);
+        }
+        counter.set_up(false);
+        for i in (12..16).rev() {
+            counter.set_clk(true);
+            counter.set_clk(false);
+            assert_eq!(to_u64(&counter.output()), i);
+        }
+        counter.set_enable(false);
+        counter.set_clk(true);
+        counter.set_clk(false);
+        assert_eq!(to_u64(&counter.output()), 12);
+        assert!(!counter.terminal_count());
+        counter.set_enable(true);
+        counter.set_clk(true);
+        counter.set_clk(false);
+        assert_eq!(to_u64(&counter.output()), 11);
+        assert!(!counter.terminal_count());
+        counter.set_reset(true);
+        counter.set_reset(false);
+        assert_eq!(to_u64(&counter.output()), 0);
+        assert!(counter.terminal_count());
+        counter.set_enable(false);
+        assert!(!counter.terminal_count());
+        counter.set_enable(true);
+
+        // The load takes effect without a clock edge and counting resumes from the data.
+        counter.set_data([true, true, false, true]);
+        counter.set_clk(true);
+        counter.set_load(true);
+        assert_eq!(to_u64(&counter.output()), 11);
+        counter.set_clk(false);
+        counter.set_clk(true);
+        assert_eq!(to_u64(&counter.output()), 11);
+        counter.set_load(false);
+        counter.set_clk(false);
+        assert_eq!(to_u64(&counter.output()), 11);
+        counter.set_clk(true);
+        counter.set_clk(false);
+        assert_eq!(to_u64(&counter.output()), 10);
+        // Holding load also masks the stage clocks that flipping the direction can raise.
+        counter.set_data([false, false, false, false]);
+        counter.set_load(true);
+        counter.set_up(true);
+        counter.set_load(false);
+        for i in 1..=3 {
+            counter.set_clk(true);
+            counter.set_clk(false);
+            assert_eq!(to_u64(&counter.output()), i);
+        }
+        counter.set_data([true; 4]);
+        counter.set_load(true);
+        counter.set_reset(true);
+        assert_eq!(to_u64(&counter.output()), 0);
+        counter.set_reset(false);
+        assert_eq!(to_u64(&counter.output()), 15);
+        counter.set_load(false);
+        assert!(counter.terminal_count());
+    }
+
+    #[test]
+    fn test_synchronous_counter() {
+        let mut counter = SynchronousCounter::<4>::new();
+        for i in 1..=20 {
+            counter.set_clk(true);
+            counter.set_clk(false);
+            assert_eq!(to_u64(&counter.output()), i % 16);
+            assert_eq!(counter.terminal_count(), i % 16 == 15);
+        }
+        counter.set_data([false, true, false, true]);
+        counter.set_load(true);
+        counter.set_clk(true);
+        counter.set_clk(false);
+        assert_eq!(to_u64(&counter.output()), 10);
+        counter.set_load(false);
+        counter.set_up(false);
+        counter.set_clk(true);
+        counter.set_clk(false);
+        assert_eq!(to_u64(&counter.output()), 9);
+        counter.set_enable(false);
+        counter.set_clk(true);
+        counter.set_clk(false);
+        assert_eq!(to_u64(&counter.output()), 9);
+        counter.set_reset(true);
+        counter.set_reset(false);
+        assert_eq!(to_u64(&counter.output()), 0);
+        assert!(!counter.terminal_count());
+        counter.set_enable(true);
+        assert!(counter.terminal_count());
+        counter.set_clk(true);
+        counter.set_clk(false);
+        assert_eq!(to_u64(&counter.output()), 15);
+    }
+}
@@ -0,0 +1,419 @@
+use crate::{
+    bits::from_u64,
+    bus::{Bus, Logic, TriStateBuffer},
+    counter::SynchronousCounter,
+    flip_flop::ResettableDFlipFlop,
+    gate::{AndGate, NotGate},
+    memory::{GateRom, Rom},
+    multiplexer::{OneBitMultiplexer, TwoBitDemultiplexer, TwoBitMultiplexer},
+};
+
+pub trait Cpu {
+    type State;
+    /// Puts the CPU into its power-on state.
+    fn reset(&mut self);
+    /// One full clock cycle: a rising edge followed by a falling edge.
+    fn step(&mut self) {
+        self.tick(true);
+        self.tick(false);
+    }
+    fn tick(&mut self, clk: bool);
+    fn state(&self) -> Self::State;
+}
+
+pub struct OneBitCPU {
+    dff: ResettableDFlipFlop,
+}
+impl OneBitCPU {
+    pub fn new() -> OneBitCPU {
+        OneBitCPU {
+            dff: ResettableDFlipFlop::new(),
+        }
+    }
+    fn update(&mut self) {
+        let ouput = self.dff.output();
+        let not = NotGate::new(ouput);
+        self.dff.set_d(not.output());
+    }
+}
+impl Default for OneBitCPU {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl Cpu for OneBitCPU {
+    type State = bool;
+    fn reset(&mut self) {
+        self.dff.set_preset(true);
+        self.dff.set_preset(false);
+        self.update();
+    }
+    fn tick(&mut self, clk: bool) {
+        self.dff.set_clk(clk);
+        self.update();
+    }
+    fn state(&self) -> bool {
+        self.dff.output()
+    }
+}
+
+// Power-on presets register 0 and clears the rest so there is a value to move around.
+fn reset_registers(dff: &mut [ResettableDFlipFlop; 4]) {
+    for (i, dff) in dff.iter_mut().enumerate() {
+        if i == 0 {
+            dff.set_preset(true);
+            dff.set_preset(false);
+        } else {
+            dff.set_clear(true);
+            dff.set_clear(false);
+        }
+    }
+}
+
+pub struct OneBitFourRegisterCPU {
+    dff: [ResettableDFlipFlop; 4],
+    sy: (bool, bool),
+    sx: (bool, bool),
+}
+impl OneBitFourRegisterCPU {
+    pub fn new() -> OneBitFourRegisterCPU {
+        OneBitFourRegisterCPU {
+            dff: std::array::from_fn(|_| ResettableDFlipFlop::new()),
+            sy: (false, false),
+            sx: (false, false),
+        }
+    }
+    /// `sy` selects the source register and `sx` the destination.
+    pub fn set_sel(&mut self, sy: (bool, bool), sx: (bool, bool)) {
+        self.sy = sy;
+        self.sx = sx;
+        self.update();
+    }
+    fn update(&mut self) {
+        let two_bit_mux = TwoBitMultiplexer::new(
+            self.dff[0].output(),
+            self.dff[1].output(),
+            self.dff[2].output(),
+            self.dff[3].output(),
+            self.sy,
+        );
+        let demux = TwoBitDemultiplexer::new(self.sx).output();
+        let load = [demux.0, demux.1, demux.2, demux.3];
+        for (dff, load) in self.dff.iter_mut().zip(load) {
+            let one_bit_mux = OneBitMultiplexer::new(dff.output(), two_bit_mux.output(), load);
+            dff.set_d(one_bit_mux.output());
+        }
+    }
+}
+impl Default for OneBitFourRegisterCPU {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl Cpu for OneBitFourRegisterCPU {
+    type State = [bool; 4];
+    fn reset(&mut self) {
+        reset_registers(&mut self.dff);
+        self.update();
+    }
+    fn tick(&mut self, clk: bool) {
+        self.dff.iter_mut().for_each(|dff| dff.set_clk(clk));
+        self.update();
+    }
+    fn state(&self) -> [bool; 4] {
+        std::array::from_fn(|i| self.dff[i].output())
+    }
+}
+
+/// Instruction words for the four-register CPUs are one byte: the source register in bits
+/// 0-1, the destination in bits 2-3 and the opcode in bits 4-7. NOP is 0x00 and MOV is 0x1_.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FourRegisterInstruction {
+    Nop,
+    Mov { dst: u8, src: u8 },
+}
+impl FourRegisterInstruction {
+    pub fn encode(self) -> u8 {
+        match self {
+            FourRegisterInstruction::Nop => 0x00,
+            FourRegisterInstruction::Mov { dst, src } => 0x10 | (dst & 3) << 2 | (src & 3),
+        }
+    }
+}
+
+/// Drives `sy`/`sx` from an instruction word. Anything other than MOV, including undefined
+/// opcodes, moves the destination onto itself and so leaves every register unchanged.
+pub struct InstructionDecoder {
+    word: [bool; 8],
+}
+impl InstructionDecoder {
+    pub fn new(word: [bool; 8]) -> InstructionDecoder {
+        InstructionDecoder { word }
+    }
+    pub fn set_word(&mut self, word: [bool; 8]) {
+        self.word = word;
+    }
+    pub fn word(&self) -> [bool; 8] {
+        self.word
+    }
+    pub fn is_mov(&self) -> bool {
+        let not_op1 = NotGate::new(self.word[5]);
+        let not_op2 = NotGate::new(self.word[6]);
+        let not_op3 = NotGate::new(self.word[7]);
+        let low = AndGate::new(self.word[4], not_op1.output());
+        let high = AndGate::new(not_op2.output(), not_op3.output());
+        AndGate::new(low.output(), high.output()).output()
+    }
+    pub fn output(&self) -> ((bool, bool), (bool, bool)) {
+        let is_mov = self.is_mov();
+        let sy0 = OneBitMultiplexer::new(self.word[2], self.word[0], is_mov);
+        let sy1 = OneBitMultiplexer::new(self.word[3], self.word[1], is_mov);
+        ((sy0.output(), sy1.output()), (self.word[2], self.word[3]))
+    }
+}
+
+/// Fetches instruction words from a 16-word ROM addressed by a program counter that advances
+/// every clock, and decodes them onto the select lines of a `OneBitFourRegisterCPU`.
+pub struct OneBitFourRegisterRomCPU {
+    cpu: OneBitFourRegisterCPU,
+    rom: GateRom<4, 16, 8>,
+    pc: SynchronousCounter<4>,
+}
+impl OneBitFourRegisterRomCPU {
+    pub fn new(program: [u8; 16]) -> OneBitFourRegisterRomCPU {
+        let words = program.map(|byte| from_u64(byte as u64)).to_vec();
+        let mut cpu = OneBitFourRegisterRomCPU {
+            cpu: OneBitFourRegisterCPU::new(),
+            rom: GateRom::from_words(words),
+            pc: SynchronousCounter::new(),
+        };
+        cpu.update();
+        cpu
+    }
+    pub fn pc(&self) -> [bool; 4] {
+        self.pc.output()
+    }
+    fn update(&mut self) {
+        self.rom.set_addr(self.pc.output());
+        let decoder = InstructionDecoder::new(self.rom.output());
+        let (sy, sx) = decoder.output();
+        self.cpu.set_sel(sy, sx);
+    }
+}
+impl Cpu for OneBitFourRegisterRomCPU {
+    type State = [bool; 4];
+    fn reset(&mut self) {
+        self.pc.set_reset(true);
+        self.pc.set_reset(false);
+        self.cpu.reset();
+        self.update();
+    }
+    fn tick(&mut self, clk: bool) {
+        self.cpu.tick(clk);
+        self.pc.set_clk(clk);
+        self.update();
+    }
+    fn state(&self) -> [bool; 4] {
+        self.cpu.state()
+    }
+}
+
+pub struct OneBitFourRegisterBusCPU {
+    dff: [ResettableDFlipFlop; 4],
+    bus: Bus<1>,
+    sy: (bool, bool),
+    sx: (bool, bool),
+}
+impl OneBitFourRegisterBusCPU {
+    pub fn new() -> OneBitFourRegisterBusCPU {
+        OneBitFourRegisterBusCPU {
+            dff: std::array::from_fn(|_| ResettableDFlipFlop::new()),
+            bus: Bus::new(),
+            sy: (false, false),
+            sx: (false, false),
+        }
+    }
+    pub fn set_sel(&mut self, sy: (bool, bool), sx: (bool, bool)) {
+        self.sy = sy;
+        self.sx = sx;
+        self.update();
+    }
+    pub fn bus(&self) -> Logic {
+        self.bus.output()[0]
+    }
+    fn update(&mut self) {
+        // The source register drives the shared bus; every other buffer stays in high-Z.
+        let read = TwoBitDemultiplexer::new(self.sy).output();
+        let read = [read.0, read.1, read.2, read.3];
+        self.bus.release();
+        for (dff, enable) in self.dff.iter().zip(read) {
+            let buffer = TriStateBuffer::new(dff.output(), enable);
+            self.bus.drive([buffer.output()]);
+        }
+        let value = self
+            .bus
+            .read()
+            .expect("exactly one register drives the bus")[0];
+        let write = TwoBitDemultiplexer::new(self.sx).output();
+        let write = [write.0, write.1, write.2, write.3];
+        for (dff, load) in self.dff.iter_mut().zip(write) {
+            let mux = OneBitMultiplexer::new(dff.output(), value, load);
+            dff.set_d(mux.output());
+        }
+    }
+}
+impl Default for OneBitFourRegisterBusCPU {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl Cpu for OneBitFourRegisterBusCPU {
+    type State = [bool; 4];
+    fn reset(&mut self) {
+        reset_registers(&mut self.dff);
+        self.update();
+    }
+    fn tick(&mut self, clk: bool) {
+        self.dff.iter_mut().for_each(|dff| dff.set_clk(clk));
+        self.update();
+    }
+    fn state(&self) -> [bool; 4] {
+        std::array::from_fn(|i| self.dff[i].output())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MOVES: [((bool, bool), (bool, bool)); 3] = [
+        ((false, false), (true, false)),
+        ((true, false), (false, true)),
+        ((false, true), (true, true)),
+    ];
+
+    #[test]
+    fn test_one_bit_cpu() {
+        let mut cpu = OneBitCPU::new();
+        cpu.reset();
+        let mut states = vec![cpu.state()];
+        for _ in 0..4 {
+            cpu.step();
+            states.push(cpu.state());
+        }
+        assert_eq!(states, [true, false, true, false, true]);
+    }
+
+    #[test]
+    fn test_one_bit_four_register_cpu() {
+        let mut cpu = OneBitFourRegisterCPU::new();
+        assert_eq!(cpu.state(), [false, false, false, false]);
+        cpu.reset();
+        assert_eq!(cpu.state(), [true, false, false, false]);
+        cpu.step();
+        assert_eq!(cpu.state(), [true, false, false, false]);
+        let mut states = vec![];
+        for (sy, sx) in MOVES {
+            cpu.set_sel(sy, sx);
+            cpu.step();
+            states.push(cpu.state());
+        }
+        assert_eq!(
+            states,
+            [
+                [true, true, false, false],
+                [true, true, true, false],
+                [true, true, true, true],
+            ]
+        );
+        cpu.reset();
+        assert_eq!(cpu.state(), [true, false, false, false]);
+    }
+
+    #[test]
+    fn test_instruction_decoder() {
+        let mov = FourRegisterInstruction::Mov { dst: 2, src: 1 }.encode();
+        assert_eq!(mov, 0x19);
+        let decoder = InstructionDecoder::new(from_u64(mov as u64));
+        assert!(decoder.is_mov());
+        assert_eq!(decoder.output(), ((true, false), (false, true)));
+        for (dst, src) in [(0, 0), (1, 0), (2, 1), (3, 2), (0, 3)] {
+            let word = FourRegisterInstruction::Mov { dst, src }.encode();
+            let (sy, sx) = InstructionDecoder::new(from_u64(word as u64)).output();
+            assert_eq!(sy, (src & 1 == 1, src & 2 == 2));
+            assert_eq!(sx, (dst & 1 == 1, dst & 2 == 2));
+        }
+        // NOP and undefined opcodes select the destination as the source.
+        for word in [FourRegisterInstruction::Nop.encode(), 0x39, 0x99, 0xf9] {
+            let decoder = InstructionDecoder::new(from_u64(word as u64));
+            assert!(!decoder.is_mov());
+            let (sy, sx) = decoder.output();
+            assert_eq!(sy, sx);
+        }
+    }
+
+    #[test]
+    fn test_one_bit_four_register_rom_cpu() {
+        let mut program = [0; 16];
+        program[1] = FourRegisterInstruction::Mov { dst: 1, src: 0 }.encode();
+        program[2] = FourRegisterInstruction::Mov { dst: 2, src: 1 }.encode();
+        program[3] = FourRegisterInstruction::Mov { dst: 3, src: 2 }.encode();
+        program[4] = FourRegisterInstruction::Mov { dst: 0, src: 3 }.encode();
+        program[5] = FourRegisterInstruction::Mov { dst: 2, src: 0 }.encode();
+        let mut cpu = OneBitFourRegisterRomCPU::new(program);
+        cpu.reset();
+        let mut states = vec![cpu.state()];
+        for _ in 0..4 {
+            cpu.step();
+            states.push(cpu.state());
+        }
+        assert_eq!(
+            states,
+            [
+                [true, false, false, false],
+                [true, false, false, false],
+                [true, true, false, false],
+                [true, true, true, false],
+                [true, true, true, true],
+            ]
+        );
+        assert_eq!(cpu.pc(), [false, false, true, false]);
+
+        // Copying the cleared register 1 into register 0 first leaves only zeros to move.
+        program[0] = FourRegisterInstruction::Mov { dst: 0, src: 1 }.encode();
+        let mut cpu = OneBitFourRegisterRomCPU::new(program);
+        cpu.reset();
+        (0..6).for_each(|_| cpu.step());
+        assert_eq!(cpu.state(), [false, false, false, false]);
+        (0..10).for_each(|_| cpu.step());
+        assert_eq!(cpu.pc(), [false, false, false, false]);
+        assert_eq!(cpu.state(), [false, false, false, false]);
+    }
+
+    #[test]
+    fn test_one_bit_four_register_bus_cpu() {
+        let mut cpu = OneBitFourRegisterBusCPU::new();
+        cpu.reset();
+        assert_eq!(cpu.bus(), Logic::High);
+        let mut states = vec![];
+        for (sy, sx) in MOVES {
+            cpu.set_sel(sy, sx);
+            cpu.step();
+            states.push(cpu.state());
+        }
+        assert_eq!(
+            states,
+            [
+                [true, true, false, false],
+                [true, true, true, false],
+                [true, true, true, true],
+            ]
+        );
+        // Reading the cleared register d puts a low level on the bus.
+        cpu.reset();
+        cpu.set_sel((true, true), (false, false));
+        assert_eq!(cpu.bus(), Logic::Low);
+        cpu.step();
+        assert_eq!(cpu.state(), [false, false, false, false]);
+    }
+}
@@ -0,0 +1,126 @@
+use crate::{
+    gate::{AndGate, NotGate, OrGate},
+    multiplexer::Demux,
+};
+
+pub struct Decoder<const K: usize, const M: usize> {
+    a: [bool; K],
+    enable: bool,
+}
+impl<const K: usize, const M: usize> Decoder<K, M> {
+    pub fn new(a: [bool; K], enable: bool) -> Decoder<K, M> {
+        Decoder { a, enable }
+    }
+    pub fn set_a(&mut self, a: [bool; K]) {
+        self.a = a;
+    }
+    pub fn set_enable(&mut self, enable: bool) {
+        self.enable = enable;
+    }
+    pub fn a(&self) -> [bool; K] {
+        self.a
+    }
+    pub fn enable(&self) -> bool {
+        self.enable
+    }
+    pub fn output(&self) -> [bool; M] {
+        let demux = Demux::new(self.enable, self.a);
+        demux.output()
+    }
+}
+
+pub struct Encoder<const M: usize, const K: usize> {
+    a: [bool; M],
+}
+impl<const M: usize, const K: usize> Encoder<M, K> {
+    const WIDTH_CHECK: () = assert!(M == 1 << K, "Encoder<M, K> requires M == 1 << K");
+
+    pub fn new(a: [bool; M]) -> Encoder<M, K> {
+        let () = Self::WIDTH_CHECK;
+        Encoder { a }
+    }
+    pub fn set_a(&mut self, a: [bool; M]) {
+        self.a = a;
+    }
+    pub fn a(&self) -> [bool; M] {
+        self.a
+    }
+    pub fn output(&self) -> [bool; K] {
+        let mut output = [false; K];
+        (0..K).for_each(|i| {
+            output[i] = (0..M)
+                .filter(|j| (j >> i) & 1 == 1)
+                .fold(false, |acc, j| OrGate::new(acc, self.a[j]).output());
+        });
+        output
+    }
+}
+
+pub struct PriorityEncoder<const M: usize, const K: usize> {
+    a: [bool; M],
+}
+impl<const M: usize, const K: usize> PriorityEncoder<M, K> {
+    pub fn new(a: [bool; M]) -> PriorityEncoder<M, K> {
+        PriorityEncoder { a }
+    }
+    pub fn set_a(&mut self, a: [bool; M]) {
+        self.a = a;
+    }
+    pub fn a(&self) -> [bool; M] {
+        self.a
+    }
+    pub fn output(&self) -> ([bool; K], bool) {
+        // The highest active input masks every input below it.
+        let mut grant = [false; M];
+        let mut valid = false;
+        (0..M).rev().for_each(|j| {
+            let not = NotGate::new(valid);
+            let and = AndGate::new(self.a[j], not.output());
+            grant[j] = and.output();
+            let or = OrGate::new(valid, self.a[j]);
+            valid = or.output();
+        });
+        let encoder = Encoder::new(grant);
+        (encoder.output(), valid)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decoder() {
+        let mut decoder = Decoder::<2, 4>::new([true, false], true);
+        assert_eq!(decoder.output(), [false, true, false, false]);
+        decoder.set_a([true, true]);
+        assert_eq!(decoder.output(), [false, false, false, true]);
+        decoder.set_enable(false);
+        assert_eq!(decoder.output(), [false; 4]);
+    }
+
+    #[test]
+    fn test_encoder() {
+        let mut encoder = Encoder::<8, 3>::new([false; 8]);
+        (0..8).for_each(|i| {
+            let mut a = [false; 8];
+            a[i] = true;
+            encoder.set_a(a);
+            assert_eq!(encoder.output(), [i & 1 == 1, i & 2 == 2, i & 4 == 4]);
+        });
+    }
+
+    #[test]
+    fn test_priority_encoder() {
+        let mut encoder = PriorityEncoder::<4, 2>::new([false; 4]);
+        assert_eq!(encoder.output(), ([false, false], false));
+        encoder.set_a([true, false, false, false]);
+        assert_eq!(encoder.output(), ([false, false], true));
+        encoder.set_a([true, true, false, false]);
+        assert_eq!(encoder.output(), ([true, false], true));
+        encoder.set_a([true, false, true, false]);
+        assert_eq!(encoder.output(), ([false, true], true));
+        encoder.set_a([true, true, true, true]);
+        assert_eq!(encoder.output(), ([true, true], true));
+    }
+}
@@ -1,66 +1,283 @@
-use crate::gate::{NandGate, NotGate};
+use std::{error::Error, fmt};
 
-struct RSLatch {
+use crate::{
+    bus::Logic,
+    gate::{AndGate, NandGate, NorGate, NotGate, OrGate, XorGate},
+    multiplexer::OneBitMultiplexer,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LatchKind {
+    /// Active-low inputs: S=R=1 holds, S=R=0 is forbidden.
+    Nand,
+    /// Active-high inputs: S=R=0 holds, S=R=1 is forbidden.
+    Nor,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ForbiddenState;
+impl fmt::Display for ForbiddenState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "latch is set and reset at the same time")
+    }
+}
+impl Error for ForbiddenState {}
+
+pub struct SRLatch {
+    kind: LatchKind,
     s: bool,
     r: bool,
     q: bool,
     nq: bool,
 }
-impl RSLatch {
-    fn new(s: bool, r: bool) -> RSLatch {
-        if (s && r) || (!s && !r) {
-            panic!("Invalid input");
-        }
-        if s {
-            RSLatch {
-                s,
-                r,
-                q: true,
-                nq: false,
-            }
-        } else {
-            RSLatch {
-                s,
-                r,
-                q: false,
-                nq: true,
-            }
-        }
+impl SRLatch {
+    pub fn new(kind: LatchKind, s: bool, r: bool) -> SRLatch {
+        let mut latch = SRLatch {
+            kind,
+            s,
+            r,
+            q: false,
+            nq: true,
+        };
+        latch.settle();
+        latch
     }
-    fn set_s(&mut self, s: bool) {
+    pub fn set_s(&mut self, s: bool) {
         self.s = s;
-        let mut nand1 = NandGate::new(self.s, self.nq);
-        let nand2 = NandGate::new(nand1.output(), self.r);
-        nand1.set_b(nand2.output());
-        self.q = nand1.output();
-        self.nq = nand2.output();
+        self.settle();
+    }
+    pub fn set_r(&mut self, r: bool) {
+        self.r = r;
+        self.settle();
     }
-    fn set_r(&mut self, r: bool) {
+    pub fn set_inputs(&mut self, s: bool, r: bool) {
+        self.s = s;
         self.r = r;
-        let mut nand2 = NandGate::new(self.q, self.r);
-        let nand1 = NandGate::new(self.s, nand2.output());
-        nand2.set_a(nand1.output());
-        self.q = nand1.output();
-        self.nq = nand2.output();
+        self.settle();
+    }
+    pub fn s(&self) -> bool {
+        self.s
     }
-    fn output(&self) -> (bool, bool) {
+    pub fn r(&self) -> bool {
+        self.r
+    }
+    pub fn kind(&self) -> LatchKind {
+        self.kind
+    }
+    pub fn is_forbidden(&self) -> bool {
+        match self.kind {
+            LatchKind::Nand => !self.s && !self.r,
+            LatchKind::Nor => self.s && self.r,
+        }
+    }
+    pub fn output(&self) -> (bool, bool) {
         (self.q, self.nq)
     }
+    pub fn checked_output(&self) -> Result<(bool, bool), ForbiddenState> {
+        if self.is_forbidden() {
+            Err(ForbiddenState)
+        } else {
+            Ok(self.output())
+        }
+    }
+    fn settle(&mut self) {
+        // Re-evaluate the cross-coupled pair until neither output changes. The evaluation
+        // order makes leaving the forbidden state straight into hold resolve to reset.
+        loop {
+            let (q, nq) = match self.kind {
+                LatchKind::Nand => {
+                    let q = NandGate::new(self.s, self.nq).output();
+                    (q, NandGate::new(self.r, q).output())
+                }
+                LatchKind::Nor => {
+                    let nq = NorGate::new(self.s, self.q).output();
+                    (NorGate::new(self.r, nq).output(), nq)
+                }
+            };
+            if (q, nq) == (self.q, self.nq) {
+                break;
+            }
+            self.q = q;
+            self.nq = nq;
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Metastability {
+    /// Keep whatever the zero-delay gates captured.
+    Ignore,
+    /// Report the output as unknown until the next clock edge without a violation.
+    Unknown,
+    /// Resolve to a pseudo-random value from a generator seeded with this value.
+    Random(u64),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimingViolationKind {
+    Setup,
+    Hold,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TimingViolation {
+    pub kind: TimingViolationKind,
+    pub edge: u64,
+    pub d_change: u64,
+}
+impl fmt::Display for TimingViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.kind {
+            TimingViolationKind::Setup => write!(
+                f,
+                "setup violation: D changed at {}, {} before the clock edge at {}",
+                self.d_change,
+                self.edge.abs_diff(self.d_change),
+                self.edge
+            ),
+            TimingViolationKind::Hold => write!(
+                f,
+                "hold violation: D changed at {}, {} after the clock edge at {}",
+                self.d_change,
+                self.d_change.abs_diff(self.edge),
+                self.edge
+            ),
+        }
+    }
+}
+
+struct TimingCheck {
+    setup: u64,
+    hold: u64,
+    metastability: Metastability,
+    rng: u64,
+    last_d_change: Option<u64>,
+    last_edge: Option<u64>,
+    unknown: bool,
+    violations: Vec<TimingViolation>,
+}
+impl TimingCheck {
+    // Records a violation and returns the value to force the flip-flop to, if any.
+    fn violate(&mut self, kind: TimingViolationKind, edge: u64, d_change: u64) -> Option<bool> {
+        self.violations.push(TimingViolation {
+            kind,
+            edge,
+            d_change,
+        });
+        match self.metastability {
+            Metastability::Ignore => None,
+            Metastability::Unknown => {
+                self.unknown = true;
+                None
+            }
+            Metastability::Random(_) => {
+                self.rng ^= self.rng << 13;
+                self.rng ^= self.rng >> 7;
+                self.rng ^= self.rng << 17;
+                Some(self.rng & 1 == 1)
+            }
+        }
+    }
 }
 
 pub struct DFlipFlop {
     d: bool,
     clk: bool,
-    rs1: RSLatch,
-    rs2: RSLatch,
+    rs1: SRLatch,
+    rs2: SRLatch,
+    timing: Option<TimingCheck>,
 }
 impl DFlipFlop {
     pub fn new() -> DFlipFlop {
         DFlipFlop {
             d: false,
             clk: false,
-            rs1: RSLatch::new(false, true),
-            rs2: RSLatch::new(false, true),
+            rs1: SRLatch::new(LatchKind::Nand, true, true),
+            rs2: SRLatch::new(LatchKind::Nand, true, true),
+            timing: None,
+        }
+    }
+    /// Checks D changes made through `set_d_at` against rising edges made through `set_clk_at`:
+    /// D must be stable `setup` time units before and `hold` time units after each edge.
+    pub fn with_timing(setup: u64, hold: u64, metastability: Metastability) -> DFlipFlop {
+        let seed = match metastability {
+            Metastability::Random(seed) => seed,
+            _ => 0,
+        };
+        DFlipFlop {
+            timing: Some(TimingCheck {
+                setup,
+                hold,
+                metastability,
+                // xorshift never leaves zero, so mix the seed with a constant and map the one
+                // seed that cancels it to a non-zero state.
+                rng: (seed ^ 0x9e37_79b9_7f4a_7c15).max(1),
+                last_d_change: None,
+                last_edge: None,
+                unknown: false,
+                violations: Vec::new(),
+            }),
+            ..DFlipFlop::new()
+        }
+    }
+    pub fn set_d_at(&mut self, d: bool, time: u64) {
+        let changed = d != self.d;
+        self.set_d(d);
+        let Some(timing) = self.timing.as_mut() else {
+            return;
+        };
+        if !changed {
+            return;
+        }
+        timing.last_d_change = Some(time);
+        let forced = match timing.last_edge {
+            Some(edge) if time >= edge && time - edge < timing.hold => {
+                timing.violate(TimingViolationKind::Hold, edge, time)
+            }
+            _ => None,
+        };
+        if let Some(value) = forced {
+            self.force(value);
+        }
+    }
+    pub fn set_clk_at(&mut self, clk: bool, time: u64) {
+        let rising = clk && !self.clk;
+        self.set_clk(clk);
+        let Some(timing) = self.timing.as_mut() else {
+            return;
+        };
+        if !rising {
+            return;
+        }
+        timing.last_edge = Some(time);
+        let forced = match timing.last_d_change {
+            Some(d_change) if d_change <= time && time - d_change < timing.setup => {
+                timing.violate(TimingViolationKind::Setup, time, d_change)
+            }
+            _ => {
+                timing.unknown = false;
+                None
+            }
+        };
+        if let Some(value) = forced {
+            self.force(value);
+        }
+    }
+    pub fn output_logic(&self) -> Logic {
+        match &self.timing {
+            Some(timing) if timing.unknown => Logic::Unknown,
+            _ => Logic::from_bool(self.output()),
+        }
+    }
+    pub fn violations(&self) -> &[TimingViolation] {
+        match &self.timing {
+            Some(timing) => &timing.violations,
+            None => &[],
+        }
+    }
+    pub fn clear_violations(&mut self) {
+        if let Some(timing) = self.timing.as_mut() {
+            timing.violations.clear();
         }
     }
     pub fn set_d(&mut self, d: bool) {
@@ -74,18 +291,196 @@ impl DFlipFlop {
     pub fn output(&self) -> bool {
         self.rs2.output().0
     }
+    pub fn outputs(&self) -> (bool, bool) {
+        self.rs2.output()
+    }
     fn update(&mut self) {
         let clock_not1 = NotGate::new(self.clk);
         let clock_not2 = NotGate::new(clock_not1.output());
         let d_not = NotGate::new(self.d);
         let nand1 = NandGate::new(self.d, clock_not1.output());
         let nand2 = NandGate::new(d_not.output(), clock_not1.output());
-        self.rs1.set_s(nand1.output());
-        self.rs1.set_r(nand2.output());
+        self.rs1.set_inputs(nand1.output(), nand2.output());
         let nand3 = NandGate::new(self.rs1.output().0, clock_not2.output());
         let nand4 = NandGate::new(self.rs1.output().1, clock_not2.output());
-        self.rs2.set_s(nand3.output());
-        self.rs2.set_r(nand4.output());
+        self.rs2.set_inputs(nand3.output(), nand4.output());
+    }
+    // Drives both latches to `value`; whichever latch is transparent then follows its input.
+    fn force(&mut self, value: bool) {
+        self.rs1.set_inputs(!value, value);
+        self.rs2.set_inputs(!value, value);
+        self.update();
+    }
+}
+impl Default for DFlipFlop {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+pub struct ResettableDFlipFlop {
+    d: bool,
+    clk: bool,
+    enable: bool,
+    clear: bool,
+    preset: bool,
+    rs1: SRLatch,
+    rs2: SRLatch,
+}
+impl ResettableDFlipFlop {
+    pub fn new() -> ResettableDFlipFlop {
+        ResettableDFlipFlop {
+            d: false,
+            clk: false,
+            enable: true,
+            clear: false,
+            preset: false,
+            rs1: SRLatch::new(LatchKind::Nand, true, true),
+            rs2: SRLatch::new(LatchKind::Nand, true, true),
+        }
+    }
+    pub fn set_d(&mut self, d: bool) {
+        self.d = d;
+        self.update();
+    }
+    pub fn set_clk(&mut self, clk: bool) {
+        self.clk = clk;
+        self.update();
+    }
+    pub fn set_enable(&mut self, enable: bool) {
+        self.enable = enable;
+        self.update();
+    }
+    pub fn set_clear(&mut self, clear: bool) {
+        self.clear = clear;
+        self.update();
+    }
+    pub fn set_preset(&mut self, preset: bool) {
+        self.preset = preset;
+        self.update();
+    }
+    pub fn output(&self) -> bool {
+        self.rs2.output().0
+    }
+    pub fn outputs(&self) -> (bool, bool) {
+        self.rs2.output()
+    }
+    fn update(&mut self) {
+        // A disabled flip-flop reloads its own output, so the clock keeps running.
+        let hold = OneBitMultiplexer::new(self.output(), self.d, self.enable);
+        let d = hold.output();
+        let clock_not1 = NotGate::new(self.clk);
+        let clock_not2 = NotGate::new(clock_not1.output());
+        let d_not = NotGate::new(d);
+        let nand1 = NandGate::new(d, clock_not1.output());
+        let nand2 = NandGate::new(d_not.output(), clock_not1.output());
+        let (s, r) = self.force(nand1.output(), nand2.output());
+        self.rs1.set_inputs(s, r);
+        let nand3 = NandGate::new(self.rs1.output().0, clock_not2.output());
+        let nand4 = NandGate::new(self.rs1.output().1, clock_not2.output());
+        let (s, r) = self.force(nand3.output(), nand4.output());
+        self.rs2.set_inputs(s, r);
+    }
+    // Overrides active-low latch inputs: clear forces reset and takes priority over preset.
+    fn force(&self, s: bool, r: bool) -> (bool, bool) {
+        let clear_not = NotGate::new(self.clear);
+        let preset_only = AndGate::new(self.preset, clear_not.output());
+        let preset_only_not = NotGate::new(preset_only.output());
+        let s_or_clear = OrGate::new(s, self.clear);
+        let s = AndGate::new(s_or_clear.output(), preset_only_not.output());
+        let r_and_not_clear = AndGate::new(r, clear_not.output());
+        let r = OrGate::new(r_and_not_clear.output(), preset_only.output());
+        (s.output(), r.output())
+    }
+}
+impl Default for ResettableDFlipFlop {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+pub struct JKFlipFlop {
+    j: bool,
+    k: bool,
+    dff: DFlipFlop,
+}
+impl JKFlipFlop {
+    pub fn new() -> JKFlipFlop {
+        let mut jk_flip_flop = JKFlipFlop {
+            j: false,
+            k: false,
+            dff: DFlipFlop::new(),
+        };
+        jk_flip_flop.update();
+        jk_flip_flop
+    }
+    pub fn set_j(&mut self, j: bool) {
+        self.j = j;
+        self.update();
+    }
+    pub fn set_k(&mut self, k: bool) {
+        self.k = k;
+        self.update();
+    }
+    pub fn set_clk(&mut self, clk: bool) {
+        self.dff.set_clk(clk);
+        self.update();
+    }
+    pub fn output(&self) -> bool {
+        self.dff.output()
+    }
+    pub fn outputs(&self) -> (bool, bool) {
+        self.dff.outputs()
+    }
+    fn update(&mut self) {
+        // D = J·/Q + /K·Q
+        let (q, nq) = self.dff.outputs();
+        let k_not = NotGate::new(self.k);
+        let and1 = AndGate::new(self.j, nq);
+        let and2 = AndGate::new(k_not.output(), q);
+        let or = OrGate::new(and1.output(), and2.output());
+        self.dff.set_d(or.output());
+    }
+}
+impl Default for JKFlipFlop {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+pub struct TFlipFlop {
+    t: bool,
+    dff: DFlipFlop,
+}
+impl TFlipFlop {
+    pub fn new() -> TFlipFlop {
+        TFlipFlop {
+            t: false,
+            dff: DFlipFlop::new(),
+        }
+    }
+    pub fn set_t(&mut self, t: bool) {
+        self.t = t;
+        self.update();
+    }
+    pub fn set_clk(&mut self, clk: bool) {
+        self.dff.set_clk(clk);
+        self.update();
+    }
+    pub fn output(&self) -> bool {
+        self.dff.output()
+    }
+    pub fn outputs(&self) -> (bool, bool) {
+        self.dff.outputs()
+    }
+    fn update(&mut self) {
+        let xor = XorGate::new(self.t, self.dff.output());
+        self.dff.set_d(xor.output());
+    }
+}
+impl Default for TFlipFlop {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
@@ -94,19 +489,44 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_rs_latch() {
-        let mut rs_latch = RSLatch::new(true, false);
-        assert_eq!(rs_latch.output(), (true, false));
-        rs_latch.set_r(true);
-        assert_eq!(rs_latch.output(), (true, false));
-        rs_latch.set_r(false);
-        assert_eq!(rs_latch.output(), (false, true));
-        rs_latch.set_r(true);
-        assert_eq!(rs_latch.output(), (false, true));
-        rs_latch.set_s(false);
-        assert_eq!(rs_latch.output(), (true, false));
-        rs_latch.set_s(true);
-        assert_eq!(rs_latch.output(), (true, false));
+    fn test_nand_sr_latch() {
+        let mut latch = SRLatch::new(LatchKind::Nand, true, true);
+        assert_eq!(latch.output(), (false, true));
+        latch.set_s(false);
+        assert_eq!(latch.output(), (true, false));
+        latch.set_s(true);
+        assert_eq!(latch.output(), (true, false));
+        latch.set_r(false);
+        assert_eq!(latch.output(), (false, true));
+        latch.set_r(true);
+        assert_eq!(latch.output(), (false, true));
+        latch.set_inputs(false, false);
+        assert_eq!(latch.output(), (true, true));
+        assert_eq!(latch.checked_output(), Err(ForbiddenState));
+        latch.set_inputs(true, true);
+        assert_eq!(latch.checked_output(), Ok((false, true)));
+        latch.set_inputs(false, true);
+        assert_eq!(latch.checked_output(), Ok((true, false)));
+    }
+
+    #[test]
+    fn test_nor_sr_latch() {
+        let mut latch = SRLatch::new(LatchKind::Nor, false, false);
+        assert_eq!(latch.output(), (false, true));
+        latch.set_s(true);
+        assert_eq!(latch.output(), (true, false));
+        latch.set_s(false);
+        assert_eq!(latch.output(), (true, false));
+        latch.set_r(true);
+        assert_eq!(latch.output(), (false, true));
+        latch.set_r(false);
+        assert_eq!(latch.output(), (false, true));
+        latch.set_inputs(true, true);
+        assert_eq!(latch.output(), (false, false));
+        assert!(latch.is_forbidden());
+        assert_eq!(latch.checked_output(), Err(ForbiddenState));
+        latch.set_inputs(false, false);
+        assert_eq!(latch.checked_output(), Ok((false, true)));
     }
 
     #[test]
@@ -123,4 +543,151 @@ mod tests {
         d_flip_flop.set_clk(true);
         assert!(!d_flip_flop.output());
     }
+
+    #[test]
+    fn test_jk_flip_flop() {
+        let mut jk_flip_flop = JKFlipFlop::new();
+        assert_eq!(jk_flip_flop.outputs(), (false, true));
+        jk_flip_flop.set_j(true);
+        assert!(!jk_flip_flop.output());
+        jk_flip_flop.set_clk(true);
+        assert_eq!(jk_flip_flop.outputs(), (true, false));
+        jk_flip_flop.set_j(false);
+        jk_flip_flop.set_clk(false);
+        jk_flip_flop.set_clk(true);
+        assert!(jk_flip_flop.output());
+        jk_flip_flop.set_k(true);
+        jk_flip_flop.set_clk(false);
+        assert!(jk_flip_flop.output());
+        jk_flip_flop.set_clk(true);
+        assert_eq!(jk_flip_flop.outputs(), (false, true));
+        jk_flip_flop.set_j(true);
+        jk_flip_flop.set_clk(false);
+        jk_flip_flop.set_clk(true);
+        assert!(jk_flip_flop.output());
+        jk_flip_flop.set_clk(false);
+        jk_flip_flop.set_clk(true);
+        assert!(!jk_flip_flop.output());
+    }
+
+    #[test]
+    fn test_t_flip_flop() {
+        let mut t_flip_flop = TFlipFlop::new();
+        t_flip_flop.set_clk(true);
+        assert!(!t_flip_flop.output());
+        t_flip_flop.set_t(true);
+        assert!(!t_flip_flop.output());
+        t_flip_flop.set_clk(false);
+        assert!(!t_flip_flop.output());
+        t_flip_flop.set_clk(true);
+        assert_eq!(t_flip_flop.outputs(), (true, false));
+        t_flip_flop.set_clk(false);
+        t_flip_flop.set_clk(true);
+        assert_eq!(t_flip_flop.outputs(), (false, true));
+        t_flip_flop.set_t(false);
+        t_flip_flop.set_clk(false);
+        t_flip_flop.set_clk(true);
+        assert!(!t_flip_flop.output());
+    }
+
+    #[test]
+    fn test_d_flip_flop_timing() {
+        let mut d_flip_flop = DFlipFlop::with_timing(3, 2, Metastability::Ignore);
+        d_flip_flop.set_d_at(true, 10);
+        d_flip_flop.set_clk_at(true, 20);
+        d_flip_flop.set_d_at(false, 25);
+        d_flip_flop.set_clk_at(false, 30);
+        assert!(d_flip_flop.violations().is_empty());
+        assert_eq!(d_flip_flop.output_logic(), Logic::High);
+
+        d_flip_flop.set_d_at(true, 38);
+        d_flip_flop.set_clk_at(true, 40);
+        d_flip_flop.set_d_at(true, 41);
+        d_flip_flop.set_d_at(false, 41);
+        assert_eq!(
+            d_flip_flop.violations(),
+            [
+                TimingViolation {
+                    kind: TimingViolationKind::Setup,
+                    edge: 40,
+                    d_change: 38,
+                },
+                TimingViolation {
+                    kind: TimingViolationKind::Hold,
+                    edge: 40,
+                    d_change: 41,
+                },
+            ]
+        );
+        assert_eq!(
+            d_flip_flop.violations()[0].to_string(),
+            "setup violation: D changed at 38, 2 before the clock edge at 40"
+        );
+        assert!(d_flip_flop.output());
+        d_flip_flop.clear_violations();
+        assert!(d_flip_flop.violations().is_empty());
+    }
+
+    #[test]
+    fn test_d_flip_flop_metastability() {
+        let mut d_flip_flop = DFlipFlop::with_timing(3, 2, Metastability::Unknown);
+        d_flip_flop.set_d_at(true, 9);
+        d_flip_flop.set_clk_at(true, 10);
+        assert_eq!(d_flip_flop.output_logic(), Logic::Unknown);
+        d_flip_flop.set_clk_at(false, 20);
+        assert_eq!(d_flip_flop.output_logic(), Logic::Unknown);
+        d_flip_flop.set_clk_at(true, 30);
+        assert_eq!(d_flip_flop.output_logic(), Logic::High);
+
+        let resolve = |seed| {
+            let mut d_flip_flop = DFlipFlop::with_timing(3, 2, Metastability::Random(seed));
+            (0..16)
+                .map(|i| {
+                    d_flip_flop.set_d_at(i % 2 == 0, i * 10 + 9);
+                    d_flip_flop.set_clk_at(true, i * 10 + 10);
+                    d_flip_flop.set_clk_at(false, i * 10 + 15);
+                    d_flip_flop.output()
+                })
+                .collect::<Vec<_>>()
+        };
+        for seed in [1, 0x9e37_79b9_7f4a_7c15] {
+            let outputs = resolve(seed);
+            assert_eq!(outputs, resolve(seed));
+            assert!(outputs.contains(&true) && outputs.contains(&false));
+            assert_ne!(outputs, (0..16).map(|i| i % 2 == 0).collect::<Vec<_>>());
+        }
+    }
+
+    #[test]
+    fn test_resettable_d_flip_flop() {
+        let mut d_flip_flop = ResettableDFlipFlop::new();
+        d_flip_flop.set_preset(true);
+        assert_eq!(d_flip_flop.outputs(), (true, false));
+        d_flip_flop.set_clk(true);
+        assert!(d_flip_flop.output());
+        d_flip_flop.set_preset(false);
+        assert!(d_flip_flop.output());
+        d_flip_flop.set_clear(true);
+        assert_eq!(d_flip_flop.outputs(), (false, true));
+        d_flip_flop.set_preset(true);
+        assert!(!d_flip_flop.output());
+        d_flip_flop.set_preset(false);
+        d_flip_flop.set_clear(false);
+        assert!(!d_flip_flop.output());
+
+        d_flip_flop.set_clk(false);
+        d_flip_flop.set_d(true);
+        d_flip_flop.set_clk(true);
+        assert!(d_flip_flop.output());
+        d_flip_flop.set_enable(false);
+        d_flip_flop.set_d(false);
+        d_flip_flop.set_clk(false);
+        d_flip_flop.set_clk(true);
+        assert!(d_flip_flop.output());
+        d_flip_flop.set_enable(true);
+        d_flip_flop.set_clk(false);
+        assert!(d_flip_flop.output());
+        d_flip_flop.set_clk(true);
+        assert!(!d_flip_flop.output());
+    }
 }
@@ -0,0 +1,381 @@
+use crate::{
+    adder::NBitFullAdder,
+    bits::from_u64,
+    gate::{AndGate, NotGate, OrGate, XorGate},
+    multiplexer::OneBitMultiplexer,
+    shifter::BarrelShifter,
+    subtractor::{AddSubMode, NBitAdderSubtractor, NBitFullSubtractor},
+};
+
+// IEEE-754 binary16, LSB first: fraction [0..10], exponent [10..15], sign [15].
+// Exponents are carried internally as 8-bit two's complement values.
+
+pub struct Float16Adder {
+    a: [bool; 16],
+    b: [bool; 16],
+}
+impl Float16Adder {
+    pub fn new(a: [bool; 16], b: [bool; 16]) -> Self {
+        Self { a, b }
+    }
+    pub fn set_a(&mut self, a: [bool; 16]) {
+        self.a = a;
+    }
+    pub fn set_b(&mut self, b: [bool; 16]) {
+        self.b = b;
+    }
+    pub fn output(&self) -> [bool; 16] {
+        let a_magnitude: [bool; 15] = self.a[0..15].try_into().unwrap();
+        let b_magnitude: [bool; 15] = self.b[0..15].try_into().unwrap();
+        let (_, a_ge_b) = NBitFullSubtractor::new(a_magnitude, b_magnitude).output();
+        let swap = NotGate::new(a_ge_b);
+        let big = unpack(mux_bits(self.a, self.b, swap.output()));
+        let small = unpack(mux_bits(self.b, self.a, swap.output()));
+
+        let (d, _) = NBitFullSubtractor::new(big.exponent, small.exponent).output();
+        // Any distance of 13 or more leaves only sticky bits, so clamp it to 15.
+        let mut distance = [false; 4];
+        (0..4).for_each(|i| {
+            distance[i] = OrGate::new(d[i], d[4]).output();
+        });
+        let mut word = [false; 27];
+        word[16..27].copy_from_slice(&small.significand);
+        let word = shift_right_sticky(word, distance, 13);
+        let aligned: [bool; 14] = word[13..27].try_into().unwrap();
+        let mut big_significand = [false; 14];
+        big_significand[3..14].copy_from_slice(&big.significand);
+
+        let sub = XorGate::new(big.sign, small.sign);
+        let mut adder_subtractor =
+            NBitAdderSubtractor::new(big_significand, aligned, AddSubMode::Add);
+        adder_subtractor.set_sub(sub.output());
+        let (sum, carry) = adder_subtractor.output();
+        let not_sub = NotGate::new(sub.output());
+        let overflow = AndGate::new(carry, not_sub.output());
+
+        let exponent = widen(big.exponent);
+        let mut shifted = [true; 14];
+        shifted[0] = OrGate::new(sum[0], sum[1]).output();
+        shifted[1..13].copy_from_slice(&sum[2..14]);
+        let mantissa = mux_bits(sum, shifted, overflow.output());
+        let exponent = mux_bits(exponent, increment(exponent), overflow.output());
+        let (mantissa, exponent) = normalize(mantissa, exponent);
+
+        let cancelled = AndGate::new(sub.output(), NotGate::new(any(&sum)).output());
+        let sign = AndGate::new(big.sign, NotGate::new(cancelled.output()).output());
+        let result = round_and_pack(mantissa, exponent, sign.output());
+
+        let both_inf = AndGate::new(big.is_inf, small.is_inf);
+        let inf_minus_inf = AndGate::new(both_inf.output(), sub.output());
+        let nan = OrGate::new(big.is_nan, inf_minus_inf.output());
+        let result = mux_bits(result, infinity(big.sign), big.is_inf);
+        mux_bits(result, quiet_nan(), nan.output())
+    }
+}
+
+pub struct Float16Multiplier {
+    a: [bool; 16],
+    b: [bool; 16],
+}
+impl Float16Multiplier {
+    pub fn new(a: [bool; 16], b: [bool; 16]) -> Self {
+        Self { a, b }
+    }
+    pub fn set_a(&mut self, a: [bool; 16]) {
+        self.a = a;
+    }
+    pub fn set_b(&mut self, b: [bool; 16]) {
+        self.b = b;
+    }
+    pub fn output(&self) -> [bool; 16] {
+        let a = unpack(self.a);
+        let b = unpack(self.b);
+        let sign = XorGate::new(a.sign, b.sign);
+
+        // Shift-and-add array multiplier; the product has its binary point at bit 20.
+        let mut product = [false; 22];
+        (0..11).for_each(|i| {
+            let mut partial = [false; 22];
+            (0..11).for_each(|j| {
+                partial[i + j] = AndGate::new(a.significand[j], b.significand[i]).output();
+            });
+            product = NBitFullAdder::new(product, partial).output().0;
+        });
+        // Treating bit 21 as the hidden bit adds one to the exponent: ea + eb - 15 + 1.
+        let (exponent, _) = NBitFullAdder::new(widen(a.exponent), widen(b.exponent)).output();
+        let (exponent, _) = NBitFullSubtractor::new(exponent, from_u64(14)).output();
+        let (mantissa, exponent) = normalize(product, exponent);
+
+        // Results below the normal range are shifted right into subnormals by 1 - exponent.
+        let tiny = OrGate::new(exponent[7], NotGate::new(any(&exponent)).output());
+        let (amount, _) = NBitFullSubtractor::new(from_u64::<8>(1), exponent).output();
+        let mut distance = [false; 4];
+        (0..4).for_each(|i| {
+            distance[i] = AndGate::new(amount[i], tiny.output()).output();
+        });
+        let mut word = [false; 38];
+        word[16..38].copy_from_slice(&mantissa);
+        let word = shift_right_sticky(word, distance, 16);
+        let mantissa: [bool; 22] = word[16..38].try_into().unwrap();
+        let exponent = mux_bits(exponent, from_u64(1), tiny.output());
+        let result = round_and_pack(mantissa, exponent, sign.output());
+
+        let a_zero = NotGate::new(any(&self.a[0..15]));
+        let b_zero = NotGate::new(any(&self.b[0..15]));
+        let inf_times_zero1 = AndGate::new(a.is_inf, b_zero.output());
+        let inf_times_zero2 = AndGate::new(b.is_inf, a_zero.output());
+        let nan1 = OrGate::new(a.is_nan, b.is_nan);
+        let nan2 = OrGate::new(inf_times_zero1.output(), inf_times_zero2.output());
+        let nan = OrGate::new(nan1.output(), nan2.output());
+        let inf = OrGate::new(a.is_inf, b.is_inf);
+        let result = mux_bits(result, infinity(sign.output()), inf.output());
+        mux_bits(result, quiet_nan(), nan.output())
+    }
+}
+
+struct Unpacked {
+    sign: bool,
+    exponent: [bool; 5],
+    significand: [bool; 11],
+    is_inf: bool,
+    is_nan: bool,
+}
+
+fn unpack(x: [bool; 16]) -> Unpacked {
+    let hidden = any(&x[10..15]);
+    let all_ones = x[10..15]
+        .iter()
+        .fold(true, |acc, &bit| AndGate::new(acc, bit).output());
+    let fraction = any(&x[0..10]);
+    // Subnormals use the same scale as exponent 1.
+    let mut exponent: [bool; 5] = x[10..15].try_into().unwrap();
+    exponent[0] = OrGate::new(exponent[0], NotGate::new(hidden).output()).output();
+    let mut significand = [hidden; 11];
+    significand[0..10].copy_from_slice(&x[0..10]);
+    Unpacked {
+        sign: x[15],
+        exponent,
+        significand,
+        is_inf: AndGate::new(all_ones, NotGate::new(fraction).output()).output(),
+        is_nan: AndGate::new(all_ones, fraction).output(),
+    }
+}
+
+// Shifts left until the top bit is set, stopping at exponent 1 so subnormals stay subnormal.
+fn normalize<const W: usize>(
+    mut mantissa: [bool; W],
+    mut exponent: [bool; 8],
+) -> ([bool; W], [bool; 8]) {
+    (1..W).for_each(|_| {
+        let not_negative = NotGate::new(exponent[7]);
+        let above_one = AndGate::new(not_negative.output(), any(&exponent[1..7]));
+        let not_top = NotGate::new(mantissa[W - 1]);
+        let shift = AndGate::new(not_top.output(), above_one.output());
+        let mut shifted = [false; W];
+        shifted[1..W].copy_from_slice(&mantissa[0..W - 1]);
+        mantissa = mux_bits(mantissa, shifted, shift.output());
+        exponent = mux_bits(exponent, decrement(exponent), shift.output());
+    });
+    (mantissa, exponent)
+}
+
+// Rounds to nearest even on the top 11 bits of a mantissa whose hidden bit is at the top.
+fn round_and_pack<const W: usize>(
+    mantissa: [bool; W],
+    exponent: [bool; 8],
+    sign: bool,
+) -> [bool; 16] {
+    let significand: [bool; 11] = mantissa[W - 11..W].try_into().unwrap();
+    let guard = mantissa[W - 12];
+    let sticky = any(&mantissa[0..W - 12]);
+    let odd_or_sticky = OrGate::new(sticky, significand[0]);
+    let round_up = AndGate::new(guard, odd_or_sticky.output());
+    let mut increment_bits = [false; 11];
+    increment_bits[0] = round_up.output();
+    let (mut significand, carry) = NBitFullAdder::new(significand, increment_bits).output();
+    significand[10] = OrGate::new(significand[10], carry).output();
+    let exponent = mux_bits(exponent, increment(exponent), carry);
+
+    let hidden = significand[10];
+    let max_exponent = exponent[0..5]
+        .iter()
+        .fold(true, |acc, &bit| AndGate::new(acc, bit).output());
+    let too_large = OrGate::new(max_exponent, any(&exponent[5..7]));
+    let not_negative = NotGate::new(exponent[7]);
+    let overflow = AndGate::new(too_large.output(), not_negative.output());
+
+    let mut result = [sign; 16];
+    result[0..10].copy_from_slice(&significand[0..10]);
+    (0..5).for_each(|i| {
+        result[10 + i] = AndGate::new(exponent[i], hidden).output();
+    });
+    mux_bits(result, infinity(sign), overflow.output())
+}
+
+// Shifts right, folding every bit that falls below `low` into the sticky bit at `low`.
+fn shift_right_sticky<const W: usize, const S: usize>(
+    word: [bool; W],
+    amount: [bool; S],
+    low: usize,
+) -> [bool; W] {
+    let mut shifted = BarrelShifter::new(word, amount, true, false, false).output();
+    shifted[low] = any(&shifted[0..=low]);
+    shifted
+}
+
+fn widen(exponent: [bool; 5]) -> [bool; 8] {
+    let mut widened = [false; 8];
+    widened[0..5].copy_from_slice(&exponent);
+    widened
+}
+
+fn increment(exponent: [bool; 8]) -> [bool; 8] {
+    let incrementer = NBitAdderSubtractor::new(exponent, [false; 8], AddSubMode::Increment);
+    incrementer.output().0
+}
+
+fn decrement(exponent: [bool; 8]) -> [bool; 8] {
+    let decrementer = NBitAdderSubtractor::new(exponent, [false; 8], AddSubMode::Decrement);
+    decrementer.output().0
+}
+
+fn infinity(sign: bool) -> [bool; 16] {
+    let mut result = from_u64(0x7c00);
+    result[15] = sign;
+    result
+}
+
+fn quiet_nan() -> [bool; 16] {
+    from_u64(0x7e00)
+}
+
+fn any(bits: &[bool]) -> bool {
+    bits.iter()
+        .fold(false, |acc, &bit| OrGate::new(acc, bit).output())
+}
+
+fn mux_bits<const N: usize>(a: [bool; N], b: [bool; N], sel: bool) -> [bool; N] {
+    let mut result = [false; N];
+    (0..N).for_each(|i| {
+        result[i] = OneBitMultiplexer::new(a[i], b[i], sel).output();
+    });
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bits::to_u64;
+
+    fn f16_to_f32(bits: u16) -> f32 {
+        let sign = if bits & 0x8000 != 0 { -1.0 } else { 1.0 };
+        let exponent = ((bits >> 10) & 0x1f) as i32;
+        let fraction = (bits & 0x3ff) as f32;
+        match exponent {
+            0 => sign * fraction * 2f32.powi(-24),
+            0x1f if fraction == 0.0 => sign * f32::INFINITY,
+            0x1f => f32::NAN,
+            _ => sign * (1024.0 + fraction) * 2f32.powi(exponent - 25),
+        }
+    }
+
+    // f32 has more than 2 * 11 + 2 significand bits, so rounding an exact f32 sum or
+    // product again to binary16 gives the correctly rounded binary16 result.
+    fn f32_to_f16(x: f32) -> u16 {
+        if x.is_nan() {
+            return 0x7e00;
+        }
+        let sign = if x.is_sign_negative() { 0x8000 } else { 0 };
+        let a = x.abs();
+        if a >= 65520.0 {
+            return sign | 0x7c00;
+        }
+        if a < 2f32.powi(-14) {
+            return sign | (a * 2f32.powi(24)).round_ties_even() as u16;
+        }
+        let mut exponent = ((a.to_bits() >> 23) & 0xff) as i32 - 127;
+        let mut significand = (a * 2f32.powi(10 - exponent)).round_ties_even() as u16;
+        if significand == 2048 {
+            significand = 1024;
+            exponent += 1;
+        }
+        if exponent > 15 {
+            return sign | 0x7c00;
+        }
+        sign | (((exponent + 15) as u16) << 10) | (significand - 1024)
+    }
+
+    fn operands() -> Vec<u16> {
+        let mut values = vec![
+            0x0000, 0x8000, 0x0001, 0x8001, 0x03ff, 0x0400, 0x07ff, 0x3c00, 0xbc00, 0x3c01, 0x3bff,
+            0x4000, 0xc000, 0x3555, 0x7bff, 0xfbff, 0x7c00, 0xfc00, 0x7e00, 0x7c01, 0x1400, 0x0200,
+            0x5640, 0xd640,
+        ];
+        let mut state = 0x2545_f491u32;
+        (0..40).for_each(|_| {
+            state ^= state << 13;
+            state ^= state >> 17;
+            state ^= state << 5;
+            values.push(state as u16);
+        });
+        values
+    }
+
+    fn check(op: &str, a: u16, b: u16, result: [bool; 16], expected: f32) {
+        let result = to_u64(&result) as u16;
+        if expected.is_nan() {
+            assert!(f16_to_f32(result).is_nan(), "{} {:04x} {:04x}", op, a, b);
+        } else {
+            let expected = f32_to_f16(expected);
+            assert_eq!(result, expected, "{} {:04x} {:04x}", op, a, b);
+        }
+    }
+
+    #[test]
+    fn test_float16_adder() {
+        let mut adder = Float16Adder::new(from_u64(0x3c00), from_u64(0x4000));
+        assert_eq!(to_u64(&adder.output()), 0x4200);
+        adder.set_b(from_u64(0xbc00));
+        assert_eq!(to_u64(&adder.output()), 0x0000);
+        adder.set_a(from_u64(0x7c00));
+        assert_eq!(to_u64(&adder.output()), 0x7c00);
+        adder.set_b(from_u64(0xfc00));
+        assert_eq!(to_u64(&adder.output()), 0x7e00);
+    }
+
+    #[test]
+    fn test_float16_adder_against_f32() {
+        for a in operands() {
+            for b in operands() {
+                let adder = Float16Adder::new(from_u64(a as u64), from_u64(b as u64));
+                check("add", a, b, adder.output(), f16_to_f32(a) + f16_to_f32(b));
+            }
+        }
+    }
+
+    #[test]
+    fn test_float16_multiplier() {
+        let mut multiplier = Float16Multiplier::new(from_u64(0x3e00), from_u64(0x4000));
+        assert_eq!(to_u64(&multiplier.output()), 0x4200);
+        multiplier.set_b(from_u64(0x8000));
+        assert_eq!(to_u64(&multiplier.output()), 0x8000);
+        multiplier.set_a(from_u64(0x7c00));
+        assert_eq!(to_u64(&multiplier.output()), 0x7e00);
+    }
+
+    #[test]
+    fn test_float16_multiplier_against_f32() {
+        for a in operands() {
+            for b in operands() {
+                let multiplier = Float16Multiplier::new(from_u64(a as u64), from_u64(b as u64));
+                check(
+                    "mul",
+                    a,
+                    b,
+                    multiplier.output(),
+                    f16_to_f32(a) * f16_to_f32(b),
+                );
+            }
+        }
+    }
+}
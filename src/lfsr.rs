@@ -0,0 +1,315 @@
+use crate::{
+    bits::{from_u64, to_u64},
+    flip_flop::ResettableDFlipFlop,
+    gate::{AndGate, XorGate},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LfsrKind {
+    /// Tapped stages are XORed into the top stage; shifts towards the LSB.
+    Fibonacci,
+    /// The top stage is XORed into every tapped stage; shifts towards the MSB.
+    Galois,
+}
+
+/// `taps[i]` is the coefficient of x^i in the feedback polynomial; x^N is implied.
+pub struct Lfsr<const N: usize> {
+    kind: LfsrKind,
+    taps: [bool; N],
+    dff: [ResettableDFlipFlop; N],
+    serial_in: bool,
+}
+impl<const N: usize> Lfsr<N> {
+    pub fn new(kind: LfsrKind, taps: [bool; N], seed: [bool; N]) -> Self {
+        let mut lfsr = Self {
+            kind,
+            taps,
+            dff: std::array::from_fn(|_| ResettableDFlipFlop::new()),
+            serial_in: false,
+        };
+        lfsr.load(seed);
+        lfsr
+    }
+    pub fn load(&mut self, seed: [bool; N]) {
+        self.dff.iter_mut().zip(seed).for_each(|(dff, bit)| {
+            dff.set_preset(bit);
+            dff.set_clear(!bit);
+            dff.set_preset(false);
+            dff.set_clear(false);
+        });
+        self.update();
+    }
+    pub fn set_clk(&mut self, clk: bool) {
+        self.dff.iter_mut().for_each(|dff| dff.set_clk(clk));
+        self.update();
+    }
+    pub fn set_serial_in(&mut self, serial_in: bool) {
+        self.serial_in = serial_in;
+        self.update();
+    }
+    pub fn output(&self) -> [bool; N] {
+        std::array::from_fn(|i| self.dff[i].output())
+    }
+    pub fn serial_out(&self) -> bool {
+        match (N, self.kind) {
+            (0, _) => self.serial_in,
+            (_, LfsrKind::Fibonacci) => self.dff[0].output(),
+            (_, LfsrKind::Galois) => self.dff[N - 1].output(),
+        }
+    }
+    fn update(&mut self) {
+        let q = self.output();
+        let mut d = [false; N];
+        match (N, self.kind) {
+            (0, _) => {}
+            (_, LfsrKind::Fibonacci) => {
+                let feedback = (0..N).fold(self.serial_in, |acc, i| {
+                    let and = AndGate::new(self.taps[i], q[i]);
+                    XorGate::new(acc, and.output()).output()
+                });
+                d[..N - 1].copy_from_slice(&q[1..]);
+                d[N - 1] = feedback;
+            }
+            (_, LfsrKind::Galois) => {
+                let feedback = XorGate::new(q[N - 1], self.serial_in);
+                (0..N).for_each(|i| {
+                    let shifted = if i == 0 { false } else { q[i - 1] };
+                    let and = AndGate::new(self.taps[i], feedback.output());
+                    d[i] = XorGate::new(shifted, and.output()).output();
+                });
+            }
+        }
+        self.dff.iter_mut().zip(d).for_each(|(dff, d)| dff.set_d(d));
+    }
+}
+
+/// Parameters follow the usual CRC catalogue fields. `residue` is the raw register content
+/// after an error-free message followed by its CRC, used by the checker.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CrcParams {
+    pub width: usize,
+    pub poly: u64,
+    pub init: u64,
+    pub reflect: bool,
+    pub xor_out: u64,
+    pub residue: u64,
+}
+
+pub const CRC_8: CrcParams = CrcParams {
+    width: 8,
+    poly: 0x07,
+    init: 0x00,
+    reflect: false,
+    xor_out: 0x00,
+    residue: 0x00,
+};
+
+pub const CRC_16_CCITT_FALSE: CrcParams = CrcParams {
+    width: 16,
+    poly: 0x1021,
+    init: 0xffff,
+    reflect: false,
+    xor_out: 0x0000,
+    residue: 0x0000,
+};
+
+pub const CRC_32: CrcParams = CrcParams {
+    width: 32,
+    poly: 0x04c1_1db7,
+    init: 0xffff_ffff,
+    reflect: true,
+    xor_out: 0xffff_ffff,
+    residue: 0xc704_dd7b,
+};
+
+/// Serial CRC: a Galois LFSR with the message fed into its feedback, one bit per clock.
+pub struct Crc<const N: usize> {
+    params: CrcParams,
+    lfsr: Lfsr<N>,
+}
+impl<const N: usize> Crc<N> {
+    const WIDTH_CHECK: () = assert!(0 < N && N <= 64, "Crc<N> requires 0 < N <= 64");
+
+    pub fn new(params: CrcParams) -> Self {
+        let () = Self::WIDTH_CHECK;
+        assert!(params.width == N, "Crc<N> requires params.width == N");
+        Self {
+            params,
+            lfsr: Lfsr::new(
+                LfsrKind::Galois,
+                from_u64(params.poly),
+                from_u64(params.init),
+            ),
+        }
+    }
+    pub fn reset(&mut self) {
+        self.lfsr.load(from_u64(self.params.init));
+    }
+    pub fn clock_bit(&mut self, bit: bool) {
+        self.lfsr.set_serial_in(bit);
+        self.lfsr.set_clk(true);
+        self.lfsr.set_clk(false);
+    }
+    pub fn clock_byte(&mut self, byte: u8) {
+        (0..8).for_each(|i| {
+            let shift = if self.params.reflect { i } else { 7 - i };
+            self.clock_bit((byte >> shift) & 1 == 1);
+        });
+    }
+    pub fn clock_bytes(&mut self, bytes: &[u8]) {
+        bytes.iter().for_each(|&byte| self.clock_byte(byte));
+    }
+    pub fn output(&self) -> [bool; N] {
+        self.lfsr.output()
+    }
+    pub fn checksum(&self) -> u64 {
+        let mut register = self.output();
+        if self.params.reflect {
+            register.reverse();
+        }
+        to_u64(&register) ^ self.params.xor_out
+    }
+    pub fn is_valid(&self) -> bool {
+        to_u64(&self.output()) == self.params.residue
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn software_crc(params: CrcParams, bytes: &[u8]) -> u64 {
+        let width = params.width;
+        let mask = (1u64 << width) - 1;
+        let top = 1u64 << (width - 1);
+        let mut register = params.init;
+        for &byte in bytes {
+            let byte = if params.reflect {
+                byte.reverse_bits()
+            } else {
+                byte
+            };
+            for i in (0..8).rev() {
+                let bit = (byte >> i) & 1 == 1;
+                let feedback = (register & top != 0) != bit;
+                register = (register << 1) & mask;
+                if feedback {
+                    register ^= params.poly;
+                }
+            }
+        }
+        if params.reflect {
+            register = register.reverse_bits() >> (64 - width);
+        }
+        register ^ params.xor_out
+    }
+
+    fn append_crc(params: CrcParams, message: &[u8], crc: u64) -> Vec<u8> {
+        let mut codeword = message.to_vec();
+        let bytes = (0..params.width / 8).map(|i| (crc >> (8 * i)) as u8);
+        if params.reflect {
+            codeword.extend(bytes);
+        } else {
+            codeword.extend(bytes.rev());
+        }
+        codeword
+    }
+
+    #[test]
+    fn test_lfsr() {
+        for kind in [LfsrKind::Fibonacci, LfsrKind::Galois] {
+            // x^4 + x^3 + 1 is primitive, so every non-zero state is visited.
+            let mut lfsr = Lfsr::new(
+                kind,
+                [true, false, false, true],
+                [true, false, false, false],
+            );
+            let mut states = vec![];
+            for _ in 0..15 {
+                states.push(to_u64(&lfsr.output()));
+                lfsr.set_clk(true);
+                lfsr.set_clk(false);
+            }
+            assert_eq!(to_u64(&lfsr.output()), 1);
+            states.sort();
+            assert_eq!(states, (1..16).collect::<Vec<_>>());
+        }
+
+        let mut lfsr = Lfsr::new(LfsrKind::Galois, [true, true, false], [true, false, false]);
+        let mut output = vec![];
+        for _ in 0..7 {
+            lfsr.set_clk(true);
+            lfsr.set_clk(false);
+            output.push(to_u64(&lfsr.output()));
+        }
+        assert_eq!(output, [2, 4, 3, 6, 7, 5, 1]);
+
+        let mut lfsr = Lfsr::new(LfsrKind::Galois, [], []);
+        lfsr.set_serial_in(true);
+        lfsr.set_clk(true);
+        assert!(lfsr.serial_out());
+    }
+
+    #[test]
+    fn test_crc_check_values() {
+        let mut crc8 = Crc::<8>::new(CRC_8);
+        crc8.clock_bytes(b"123456789");
+        assert_eq!(crc8.checksum(), 0xf4);
+
+        let mut crc16 = Crc::<16>::new(CRC_16_CCITT_FALSE);
+        crc16.clock_bytes(b"123456789");
+        assert_eq!(crc16.checksum(), 0x29b1);
+
+        let mut crc32 = Crc::<32>::new(CRC_32);
+        crc32.clock_bytes(b"123456789");
+        assert_eq!(crc32.checksum(), 0xcbf4_3926);
+        crc32.reset();
+        crc32.clock_bytes(b"");
+        assert_eq!(crc32.checksum(), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "Crc<N> requires params.width == N")]
+    fn test_crc_width_mismatch() {
+        Crc::<16>::new(CRC_32);
+    }
+
+    #[test]
+    fn test_crc_against_software() {
+        let messages: [&[u8]; 3] = [
+            b"junkcpu",
+            &[0x00, 0xff, 0x80, 0x01],
+            b"The quick brown fox",
+        ];
+        for message in messages {
+            let mut crc8 = Crc::<8>::new(CRC_8);
+            crc8.clock_bytes(message);
+            let checksum = software_crc(CRC_8, message);
+            assert_eq!(crc8.checksum(), checksum);
+            crc8.reset();
+            crc8.clock_bytes(&append_crc(CRC_8, message, checksum));
+            assert!(crc8.is_valid());
+
+            let mut crc16 = Crc::<16>::new(CRC_16_CCITT_FALSE);
+            crc16.clock_bytes(message);
+            let checksum = software_crc(CRC_16_CCITT_FALSE, message);
+            assert_eq!(crc16.checksum(), checksum);
+            crc16.reset();
+            crc16.clock_bytes(&append_crc(CRC_16_CCITT_FALSE, message, checksum));
+            assert!(crc16.is_valid());
+
+            let mut crc32 = Crc::<32>::new(CRC_32);
+            crc32.clock_bytes(message);
+            let checksum = software_crc(CRC_32, message);
+            assert_eq!(crc32.checksum(), checksum);
+            crc32.reset();
+            let mut codeword = append_crc(CRC_32, message, checksum);
+            crc32.clock_bytes(&codeword);
+            assert!(crc32.is_valid());
+            codeword[0] ^= 0x10;
+            crc32.reset();
+            crc32.clock_bytes(&codeword);
+            assert!(!crc32.is_valid());
+        }
+    }
+}
@@ -1,5 +1,20 @@
 pub mod adder;
+pub mod alu;
+pub mod bcd;
+pub mod bits;
+pub mod bus;
+pub mod clock;
+pub mod counter;
+pub mod cpu;
+pub mod encoder;
 pub mod flip_flop;
+pub mod float;
 pub mod gate;
+pub mod lfsr;
+pub mod memory;
 pub mod multiplexer;
+pub mod register;
+pub mod shift_register;
+pub mod shifter;
 pub mod subtractor;
+pub mod td4;
@@ -1,157 +1,141 @@
-use std::{
-    sync::{Arc, Mutex},
-    thread,
-};
+use std::time::Duration;
 
 use junkcpu::{
-    flip_flop::DFlipFlop,
-    gate::NotGate,
-    multiplexer::{OneBitMultiplexer, TwoBitDemultiplexer, TwoBitMultiplexer},
+    bits::to_u64,
+    clock::{Clock, Scheduler},
+    cpu::{
+        Cpu, FourRegisterInstruction, OneBitCPU, OneBitFourRegisterBusCPU, OneBitFourRegisterCPU,
+        OneBitFourRegisterRomCPU,
+    },
+    td4::{Td4, Td4Instruction},
 };
 
-struct OneBitCPU {
-    dff: DFlipFlop,
-}
-impl OneBitCPU {
-    pub fn new() -> OneBitCPU {
-        OneBitCPU {
-            dff: DFlipFlop::new(),
-        }
-    }
-    pub fn on(&mut self) {
-        self.dff.set_d(true);
-    }
-    pub fn tick(&mut self, clk: bool) {
-        self.dff.set_clk(clk);
-        let ouput = self.dff.output();
-        let not = NotGate::new(ouput);
-        self.dff.set_d(not.output());
-    }
-    pub fn dump(&self) {
-        let output = self.dff.output();
-        println!("Q: {}", output);
+fn dump_registers(state: [bool; 4]) {
+    for (i, output) in state.iter().enumerate() {
+        println!("Q{}: {}", i, output);
     }
 }
 
 fn one_bit_cpu_sample() {
-    let cpu = Arc::new(Mutex::new(OneBitCPU::new()));
-    cpu.lock().unwrap().on();
+    let mut cpu = OneBitCPU::new();
+    cpu.reset();
+    println!("Q: {}", cpu.state());
 
-    let clock_sec = 2;
-    let cpu_clone = cpu.clone();
-    let mut clk = false;
-    thread::spawn(move || loop {
-        thread::sleep(std::time::Duration::from_secs(clock_sec));
-        clk = !clk;
-        cpu_clone.lock().unwrap().tick(clk);
-        println!("tick {}", clk);
-    });
-
-    loop {
-        cpu.lock().unwrap().dump();
-        thread::sleep(std::time::Duration::from_millis(500));
+    // One simulated time unit is a second, giving a 2s high / 2s low clock.
+    let mut scheduler = Scheduler::new();
+    scheduler.add_clock(Clock::new(4));
+    scheduler.set_pacing(Some(Duration::from_secs(1)));
+    while let Some(event) = scheduler.step() {
+        cpu.tick(event.level);
+        println!("tick {}", event.level);
+        println!("Q: {}", cpu.state());
     }
 }
 
-struct OneBitFourRegisterCPU {
-    dff: [DFlipFlop; 4],
-    sy: (bool, bool),
-    sx: (bool, bool),
-}
-impl OneBitFourRegisterCPU {
-    pub fn new() -> OneBitFourRegisterCPU {
-        OneBitFourRegisterCPU {
-            dff: [
-                DFlipFlop::new(),
-                DFlipFlop::new(),
-                DFlipFlop::new(),
-                DFlipFlop::new(),
-            ],
-            sy: (false, false),
-            sx: (false, false),
-        }
-    }
-    pub fn on(&mut self) {
-        self.dff[0].set_d(true);
-    }
-    pub fn set_sel(&mut self, sy: (bool, bool), sx: (bool, bool)) {
-        self.sy = sy;
-        self.sx = sx;
-    }
-    pub fn tick(&mut self, clk: bool) {
-        for i in 0..4 {
-            self.dff[i].set_clk(clk);
-        }
-        let two_bit_mux = TwoBitMultiplexer::new(
-            self.dff[0].output(),
-            self.dff[1].output(),
-            self.dff[2].output(),
-            self.dff[3].output(),
-            self.sy,
-        );
-        let demux = TwoBitDemultiplexer::new(self.sx);
-        let one_bit_mux0 =
-            OneBitMultiplexer::new(self.dff[0].output(), two_bit_mux.output(), demux.output().0);
-        let one_bit_mux1 =
-            OneBitMultiplexer::new(self.dff[1].output(), two_bit_mux.output(), demux.output().1);
-        let one_bit_mux2 =
-            OneBitMultiplexer::new(self.dff[2].output(), two_bit_mux.output(), demux.output().2);
-        let one_bit_mux3 =
-            OneBitMultiplexer::new(self.dff[3].output(), two_bit_mux.output(), demux.output().3);
-        self.dff[0].set_d(one_bit_mux0.output());
-        self.dff[1].set_d(one_bit_mux1.output());
-        self.dff[2].set_d(one_bit_mux2.output());
-        self.dff[3].set_d(one_bit_mux3.output());
-    }
-    pub fn dump(&self) {
-        for i in 0..4 {
-            let output = self.dff[i].output();
-            println!("Q{}: {}", i, output);
-        }
-    }
-}
+// (name, sy, sx)
+type Move = (&'static str, (bool, bool), (bool, bool));
+
+const MOVES: [Move; 3] = [
+    ("mov a, b", (false, false), (true, false)),
+    ("mov b, c", (true, false), (false, true)),
+    ("mov c, d", (false, true), (true, true)),
+];
 
 fn one_bit_four_register_cpu_sample() {
     let mut cpu = OneBitFourRegisterCPU::new();
-    cpu.dump();
+    dump_registers(cpu.state());
     println!("--------------------");
-    cpu.on();
+    cpu.reset();
     println!("on");
-    cpu.dump();
-    println!("--------------------");
-    cpu.tick(true);
-    println!("tick");
-    cpu.dump();
-    println!("--------------------");
-    println!("mov a, b");
-    cpu.set_sel((false, false), (true, false));
-    cpu.tick(false);
-    cpu.dump();
-    println!("--------------------");
-    cpu.tick(true);
+    cpu.step();
     println!("tick");
-    cpu.dump();
+    dump_registers(cpu.state());
     println!("--------------------");
-    println!("mov b, c");
-    cpu.set_sel((true, false), (false, true));
-    cpu.tick(false);
-    cpu.dump();
-    println!("--------------------");
-    cpu.tick(true);
+    for (name, sy, sx) in MOVES {
+        println!("{}", name);
+        cpu.set_sel(sy, sx);
+        cpu.step();
+        dump_registers(cpu.state());
+        println!("--------------------");
+    }
+}
+
+fn one_bit_four_register_bus_cpu_sample() {
+    let mut cpu = OneBitFourRegisterBusCPU::new();
+    cpu.reset();
+    println!("on");
+    cpu.step();
     println!("tick");
-    cpu.dump();
+    dump_registers(cpu.state());
+    println!("BUS: {:?}", cpu.bus());
     println!("--------------------");
-    println!("mov c, d");
-    cpu.set_sel((false, true), (true, true));
-    cpu.tick(false);
-    cpu.dump();
-    println!("--------------------");
-    cpu.tick(true);
-    println!("tick");
-    cpu.dump();
+    for (name, sy, sx) in MOVES {
+        println!("{}", name);
+        cpu.set_sel(sy, sx);
+        cpu.step();
+        dump_registers(cpu.state());
+        println!("BUS: {:?}", cpu.bus());
+        println!("--------------------");
+    }
+}
+
+fn one_bit_four_register_rom_cpu_sample() {
+    let instructions = [
+        FourRegisterInstruction::Mov { dst: 1, src: 0 },
+        FourRegisterInstruction::Mov { dst: 2, src: 1 },
+        FourRegisterInstruction::Mov { dst: 3, src: 2 },
+    ];
+    let mut program = [0; 16];
+    for (byte, instruction) in program.iter_mut().zip(instructions) {
+        *byte = instruction.encode();
+    }
+    let mut cpu = OneBitFourRegisterRomCPU::new(program);
+    cpu.reset();
+    println!("on");
+    dump_registers(cpu.state());
     println!("--------------------");
+    for _ in instructions {
+        println!("PC: {}", to_u64(&cpu.pc()));
+        cpu.step();
+        dump_registers(cpu.state());
+        println!("--------------------");
+    }
+}
+
+fn td4_sample() {
+    // Counts up on the output port until the B register wraps, then halts.
+    let instructions = [
+        Td4Instruction::OutB,
+        Td4Instruction::AddB(1),
+        Td4Instruction::Jnc(0),
+        Td4Instruction::Jmp(3),
+    ];
+    let mut program = [0; 16];
+    for (byte, instruction) in program.iter_mut().zip(instructions) {
+        *byte = instruction.encode();
+    }
+    let mut cpu = Td4::new(program);
+    cpu.reset();
+    for _ in 0..16 * 3 {
+        cpu.step();
+        let state = cpu.state();
+        println!(
+            "PC: {:2} A: {:2} B: {:2} C: {} OUT: {:04b}",
+            to_u64(&state.pc),
+            to_u64(&state.a),
+            to_u64(&state.b),
+            state.carry as u8,
+            to_u64(&state.out)
+        );
+    }
 }
 
 fn main() {
-    one_bit_four_register_cpu_sample();
+    match std::env::args().nth(1).as_deref() {
+        Some("one-bit") => one_bit_cpu_sample(),
+        Some("bus") => one_bit_four_register_bus_cpu_sample(),
+        Some("rom") => one_bit_four_register_rom_cpu_sample(),
+        Some("td4") => td4_sample(),
+        _ => one_bit_four_register_cpu_sample(),
+    }
 }
@@ -0,0 +1,366 @@
+use std::{error::Error, fmt, fs, io, path::Path};
+
+use crate::{
+    bits::to_u64,
+    bus::{Logic, NBitTriStateBuffer},
+    encoder::Decoder,
+    flip_flop::{LatchKind, SRLatch},
+    gate::{AndGate, NandGate, NotGate, OrGate},
+    multiplexer::BusMux,
+};
+
+/// Asynchronous SRAM-style interface: writes happen while chip select and write enable are
+/// both high, and the data outputs float unless the chip is selected and being read.
+pub trait Ram<const A: usize, const N: usize> {
+    fn set_addr(&mut self, addr: [bool; A]);
+    fn set_data(&mut self, data: [bool; N]);
+    fn set_chip_select(&mut self, chip_select: bool);
+    fn set_write_enable(&mut self, write_enable: bool);
+    fn set_output_enable(&mut self, output_enable: bool);
+    fn output(&self) -> [Logic; N];
+}
+
+pub struct GateRam<const A: usize, const M: usize, const N: usize> {
+    cells: Vec<[SRLatch; N]>,
+    addr: [bool; A],
+    data: [bool; N],
+    chip_select: bool,
+    write_enable: bool,
+    output_enable: bool,
+}
+impl<const A: usize, const M: usize, const N: usize> GateRam<A, M, N> {
+    pub fn new() -> Self {
+        Self {
+            cells: (0..M)
+                .map(|_| std::array::from_fn(|_| SRLatch::new(LatchKind::Nand, true, true)))
+                .collect(),
+            addr: [false; A],
+            data: [false; N],
+            chip_select: false,
+            write_enable: false,
+            output_enable: false,
+        }
+    }
+    fn update(&mut self) {
+        // Each cell is a gated D latch: a NAND SR latch driven by NAND(D, G) and NAND(/D, G).
+        let write = AndGate::new(self.chip_select, self.write_enable);
+        let decoder = Decoder::<A, M>::new(self.addr, write.output());
+        self.cells
+            .iter_mut()
+            .zip(decoder.output())
+            .for_each(|(row, gate)| {
+                row.iter_mut().zip(self.data).for_each(|(cell, d)| {
+                    let d_not = NotGate::new(d);
+                    let nand1 = NandGate::new(d, gate);
+                    let nand2 = NandGate::new(d_not.output(), gate);
+                    cell.set_inputs(nand1.output(), nand2.output());
+                });
+            });
+    }
+}
+impl<const A: usize, const M: usize, const N: usize> Ram<A, N> for GateRam<A, M, N> {
+    fn set_addr(&mut self, addr: [bool; A]) {
+        self.addr = addr;
+        self.update();
+    }
+    fn set_data(&mut self, data: [bool; N]) {
+        self.data = data;
+        self.update();
+    }
+    fn set_chip_select(&mut self, chip_select: bool) {
+        self.chip_select = chip_select;
+        self.update();
+    }
+    fn set_write_enable(&mut self, write_enable: bool) {
+        self.write_enable = write_enable;
+        self.update();
+    }
+    fn set_output_enable(&mut self, output_enable: bool) {
+        self.output_enable = output_enable;
+        self.update();
+    }
+    fn output(&self) -> [Logic; N] {
+        let words: [[bool; N]; M] =
+            std::array::from_fn(|i| std::array::from_fn(|j| self.cells[i][j].output().0));
+        let mux = BusMux::new(words, self.addr);
+        let read = AndGate::new(self.chip_select, self.output_enable);
+        let not_write = NotGate::new(self.write_enable);
+        let enable = AndGate::new(read.output(), not_write.output());
+        let buffer = NBitTriStateBuffer::new(mux.output(), enable.output());
+        buffer.output()
+    }
+}
+impl<const A: usize, const M: usize, const N: usize> Default for GateRam<A, M, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+pub struct BehaviouralRam<const A: usize, const N: usize> {
+    words: Vec<[bool; N]>,
+    addr: [bool; A],
+    data: [bool; N],
+    chip_select: bool,
+    write_enable: bool,
+    output_enable: bool,
+}
+impl<const A: usize, const N: usize> BehaviouralRam<A, N> {
+    pub fn new() -> Self {
+        Self {
+            words: vec![[false; N]; 1 << A],
+            addr: [false; A],
+            data: [false; N],
+            chip_select: false,
+            write_enable: false,
+            output_enable: false,
+        }
+    }
+    fn update(&mut self) {
+        if self.chip_select && self.write_enable {
+            self.words[to_u64(&self.addr) as usize] = self.data;
+        }
+    }
+}
+impl<const A: usize, const N: usize> Ram<A, N> for BehaviouralRam<A, N> {
+    fn set_addr(&mut self, addr: [bool; A]) {
+        self.addr = addr;
+        self.update();
+    }
+    fn set_data(&mut self, data: [bool; N]) {
+        self.data = data;
+        self.update();
+    }
+    fn set_chip_select(&mut self, chip_select: bool) {
+        self.chip_select = chip_select;
+        self.update();
+    }
+    fn set_write_enable(&mut self, write_enable: bool) {
+        self.write_enable = write_enable;
+        self.update();
+    }
+    fn set_output_enable(&mut self, output_enable: bool) {
+        self.output_enable = output_enable;
+        self.update();
+    }
+    fn output(&self) -> [Logic; N] {
+        if self.chip_select && self.output_enable && !self.write_enable {
+            self.words[to_u64(&self.addr) as usize].map(Logic::from_bool)
+        } else {
+            [Logic::HighZ; N]
+        }
+    }
+}
+impl<const A: usize, const N: usize> Default for BehaviouralRam<A, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Debug)]
+pub enum RomError {
+    Io(io::Error),
+    TooLarge { words: usize, capacity: usize },
+}
+impl fmt::Display for RomError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RomError::Io(err) => write!(f, "failed to read ROM image: {}", err),
+            RomError::TooLarge { words, capacity } => write!(
+                f,
+                "ROM image has {} words but the ROM holds {}",
+                words, capacity
+            ),
+        }
+    }
+}
+impl Error for RomError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            RomError::Io(err) => Some(err),
+            RomError::TooLarge { .. } => None,
+        }
+    }
+}
+impl From<io::Error> for RomError {
+    fn from(err: io::Error) -> Self {
+        RomError::Io(err)
+    }
+}
+
+/// Images store each word in ceil(N / 8) little-endian bytes; missing words read as zero.
+pub trait Rom<const A: usize, const N: usize>: Sized {
+    fn from_words(words: Vec<[bool; N]>) -> Self;
+    fn set_addr(&mut self, addr: [bool; A]);
+    fn output(&self) -> [bool; N];
+
+    fn from_bytes(bytes: &[u8]) -> Result<Self, RomError> {
+        let capacity = 1 << A;
+        let mut words = bytes
+            .chunks(N.div_ceil(8).max(1))
+            .map(|chunk| {
+                std::array::from_fn(|i| {
+                    chunk
+                        .get(i / 8)
+                        .is_some_and(|byte| (byte >> (i % 8)) & 1 == 1)
+                })
+            })
+            .collect::<Vec<[bool; N]>>();
+        if words.len() > capacity {
+            return Err(RomError::TooLarge {
+                words: words.len(),
+                capacity,
+            });
+        }
+        words.resize(capacity, [false; N]);
+        Ok(Self::from_words(words))
+    }
+    fn from_file(path: impl AsRef<Path>) -> Result<Self, RomError> {
+        let bytes = fs::read(path)?;
+        Self::from_bytes(&bytes)
+    }
+}
+
+/// Diode matrix: the address decoder drives one row per word, and each output column ORs
+/// together the rows that have a diode, i.e. a 1 bit, at that position.
+pub struct GateRom<const A: usize, const M: usize, const N: usize> {
+    diodes: Vec<[bool; N]>,
+    addr: [bool; A],
+}
+impl<const A: usize, const M: usize, const N: usize> Rom<A, N> for GateRom<A, M, N> {
+    fn from_words(mut words: Vec<[bool; N]>) -> Self {
+        words.resize(M, [false; N]);
+        Self {
+            diodes: words,
+            addr: [false; A],
+        }
+    }
+    fn set_addr(&mut self, addr: [bool; A]) {
+        self.addr = addr;
+    }
+    fn output(&self) -> [bool; N] {
+        let decoder = Decoder::<A, M>::new(self.addr, true);
+        let rows = decoder.output();
+        std::array::from_fn(|j| {
+            (0..M)
+                .filter(|&i| self.diodes[i][j])
+                .fold(false, |acc, i| OrGate::new(acc, rows[i]).output())
+        })
+    }
+}
+
+pub struct BehaviouralRom<const A: usize, const N: usize> {
+    words: Vec<[bool; N]>,
+    addr: [bool; A],
+}
+impl<const A: usize, const N: usize> Rom<A, N> for BehaviouralRom<A, N> {
+    fn from_words(mut words: Vec<[bool; N]>) -> Self {
+        words.resize(1 << A, [false; N]);
+        Self {
+            words,
+            addr: [false; A],
+        }
+    }
+    fn set_addr(&mut self, addr: [bool; A]) {
+        self.addr = addr;
+    }
+    fn output(&self) -> [bool; N] {
+        self.words[to_u64(&self.addr) as usize]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bits::from_u64;
+
+    fn write(ram: &mut impl Ram<3, 4>, addr: u64, data: u64) {
+        ram.set_addr(from_u64(addr));
+        ram.set_data(from_u64(data));
+        ram.set_write_enable(true);
+        ram.set_write_enable(false);
+    }
+
+    fn read(ram: &mut impl Ram<3, 4>, addr: u64) -> Option<u64> {
+        ram.set_addr(from_u64(addr));
+        let bits = ram.output().map(Logic::to_bool);
+        bits.iter()
+            .all(Option::is_some)
+            .then(|| to_u64(&bits.map(Option::unwrap)))
+    }
+
+    fn check_ram(ram: &mut impl Ram<3, 4>) {
+        ram.set_output_enable(true);
+        assert_eq!(read(ram, 0), None);
+        ram.set_chip_select(true);
+        assert_eq!(read(ram, 0), Some(0));
+        (0..8).for_each(|addr| write(ram, addr, (addr * 5 + 3) % 16));
+        (0..8).for_each(|addr| assert_eq!(read(ram, addr), Some((addr * 5 + 3) % 16)));
+
+        ram.set_write_enable(true);
+        assert_eq!(ram.output(), [Logic::HighZ; 4]);
+        ram.set_write_enable(false);
+        ram.set_output_enable(false);
+        assert_eq!(read(ram, 2), None);
+        ram.set_output_enable(true);
+
+        ram.set_chip_select(false);
+        write(ram, 2, 0);
+        assert_eq!(read(ram, 2), None);
+        ram.set_chip_select(true);
+        assert_eq!(read(ram, 2), Some(13));
+    }
+
+    #[test]
+    fn test_gate_ram() {
+        check_ram(&mut GateRam::<3, 8, 4>::new());
+    }
+
+    #[test]
+    fn test_behavioural_ram() {
+        check_ram(&mut BehaviouralRam::<3, 4>::new());
+    }
+
+    fn check_rom<R: Rom<4, 8>>() {
+        let image = [0x3a, 0x00, 0xff, 0x81, 0x42];
+        let mut rom = R::from_bytes(&image).unwrap();
+        (0..16).for_each(|addr| {
+            rom.set_addr(from_u64(addr));
+            let expected = image.get(addr as usize).copied().unwrap_or(0);
+            assert_eq!(to_u64(&rom.output()), expected as u64);
+        });
+        assert!(matches!(
+            R::from_bytes(&[0; 17]),
+            Err(RomError::TooLarge {
+                words: 17,
+                capacity: 16
+            })
+        ));
+    }
+
+    #[test]
+    fn test_gate_rom() {
+        check_rom::<GateRom<4, 16, 8>>();
+
+        let mut rom = GateRom::<2, 4, 12>::from_bytes(&[0x34, 0x12, 0xff, 0x0f]).unwrap();
+        assert_eq!(to_u64(&rom.output()), 0x234);
+        rom.set_addr([true, false]);
+        assert_eq!(to_u64(&rom.output()), 0xfff);
+    }
+
+    #[test]
+    fn test_behavioural_rom() {
+        check_rom::<BehaviouralRom<4, 8>>();
+
+        let path = std::env::temp_dir().join(format!("junkcpu-rom-{}.bin", std::process::id()));
+        fs::write(&path, [0x05, 0x0a]).unwrap();
+        let mut rom = BehaviouralRom::<1, 4>::from_file(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+        assert_eq!(rom.output(), [true, false, true, false]);
+        rom.set_addr([true]);
+        assert_eq!(rom.output(), [false, true, false, true]);
+        assert!(matches!(
+            BehaviouralRom::<1, 4>::from_file(&path),
+            Err(RomError::Io(_))
+        ));
+    }
+}
@@ -10,15 +10,24 @@ impl OneBitMultiplexer {
     pub fn new(a: bool, b: bool, sel: bool) -> OneBitMultiplexer {
         OneBitMultiplexer { a, b, sel }
     }
-    fn set_a(&mut self, a: bool) {
+    pub fn set_a(&mut self, a: bool) {
         self.a = a;
     }
-    fn set_b(&mut self, b: bool) {
+    pub fn set_b(&mut self, b: bool) {
         self.b = b;
     }
-    fn set_sel(&mut self, sel: bool) {
+    pub fn set_sel(&mut self, sel: bool) {
         self.sel = sel;
     }
+    pub fn a(&self) -> bool {
+        self.a
+    }
+    pub fn b(&self) -> bool {
+        self.b
+    }
+    pub fn sel(&self) -> bool {
+        self.sel
+    }
     pub fn output(&self) -> bool {
         let not = NotGate::new(self.sel);
         let and1 = AndGate::new(self.a, not.output());
@@ -39,21 +48,36 @@ impl TwoBitMultiplexer {
     pub fn new(a: bool, b: bool, c: bool, d: bool, sel: (bool, bool)) -> TwoBitMultiplexer {
         TwoBitMultiplexer { a, b, c, d, sel }
     }
-    fn set_a(&mut self, a: bool) {
+    pub fn set_a(&mut self, a: bool) {
         self.a = a;
     }
-    fn set_b(&mut self, b: bool) {
+    pub fn set_b(&mut self, b: bool) {
         self.b = b;
     }
-    fn set_c(&mut self, c: bool) {
+    pub fn set_c(&mut self, c: bool) {
         self.c = c;
     }
-    fn set_d(&mut self, d: bool) {
+    pub fn set_d(&mut self, d: bool) {
         self.d = d;
     }
     pub fn set_sel(&mut self, sel: (bool, bool)) {
         self.sel = sel;
     }
+    pub fn a(&self) -> bool {
+        self.a
+    }
+    pub fn b(&self) -> bool {
+        self.b
+    }
+    pub fn c(&self) -> bool {
+        self.c
+    }
+    pub fn d(&self) -> bool {
+        self.d
+    }
+    pub fn sel(&self) -> (bool, bool) {
+        self.sel
+    }
     pub fn output(&self) -> bool {
         let mux1 = OneBitMultiplexer::new(self.a, self.b, self.sel.0);
         let mux2 = OneBitMultiplexer::new(self.c, self.d, self.sel.0);
@@ -72,6 +96,9 @@ impl TwoBitDemultiplexer {
     pub fn set_sel(&mut self, sel: (bool, bool)) {
         self.sel = sel;
     }
+    pub fn sel(&self) -> (bool, bool) {
+        self.sel
+    }
     pub fn output(&self) -> (bool, bool, bool, bool) {
         let not1 = NotGate::new(self.sel.0);
         let not2 = NotGate::new(self.sel.1);
@@ -85,6 +112,122 @@ impl TwoBitDemultiplexer {
     }
 }
 
+/// Generic multiplexers carry both K and M = 1 << K because stable Rust cannot compute
+/// array lengths from const parameters; the relation is checked when the type is used.
+pub struct Mux<const K: usize, const M: usize> {
+    data: [bool; M],
+    sel: [bool; K],
+}
+impl<const K: usize, const M: usize> Mux<K, M> {
+    const WIDTH_CHECK: () = assert!(M == 1 << K, "Mux<K, M> requires M == 1 << K");
+
+    pub fn new(data: [bool; M], sel: [bool; K]) -> Mux<K, M> {
+        let () = Self::WIDTH_CHECK;
+        Mux { data, sel }
+    }
+    pub fn set_data(&mut self, data: [bool; M]) {
+        self.data = data;
+    }
+    pub fn set_sel(&mut self, sel: [bool; K]) {
+        self.sel = sel;
+    }
+    pub fn data(&self) -> [bool; M] {
+        self.data
+    }
+    pub fn sel(&self) -> [bool; K] {
+        self.sel
+    }
+    pub fn output(&self) -> bool {
+        let mut layer = self.data.to_vec();
+        (0..K).for_each(|i| {
+            layer = layer
+                .chunks(2)
+                .map(|pair| OneBitMultiplexer::new(pair[0], pair[1], self.sel[i]).output())
+                .collect();
+        });
+        layer[0]
+    }
+}
+
+pub struct Demux<const K: usize, const M: usize> {
+    a: bool,
+    sel: [bool; K],
+}
+impl<const K: usize, const M: usize> Demux<K, M> {
+    const WIDTH_CHECK: () = assert!(M == 1 << K, "Demux<K, M> requires M == 1 << K");
+
+    pub fn new(a: bool, sel: [bool; K]) -> Demux<K, M> {
+        let () = Self::WIDTH_CHECK;
+        Demux { a, sel }
+    }
+    pub fn set_a(&mut self, a: bool) {
+        self.a = a;
+    }
+    pub fn set_sel(&mut self, sel: [bool; K]) {
+        self.sel = sel;
+    }
+    pub fn a(&self) -> bool {
+        self.a
+    }
+    pub fn sel(&self) -> [bool; K] {
+        self.sel
+    }
+    pub fn output(&self) -> [bool; M] {
+        let mut layer = vec![self.a];
+        (0..K).rev().for_each(|i| {
+            let not = NotGate::new(self.sel[i]);
+            layer = layer
+                .iter()
+                .flat_map(|&x| {
+                    let and1 = AndGate::new(x, not.output());
+                    let and2 = AndGate::new(x, self.sel[i]);
+                    [and1.output(), and2.output()]
+                })
+                .collect();
+        });
+        let mut output = [false; M];
+        output.copy_from_slice(&layer);
+        output
+    }
+}
+
+pub struct BusMux<const K: usize, const M: usize, const N: usize> {
+    data: [[bool; N]; M],
+    sel: [bool; K],
+}
+impl<const K: usize, const M: usize, const N: usize> BusMux<K, M, N> {
+    pub fn new(data: [[bool; N]; M], sel: [bool; K]) -> BusMux<K, M, N> {
+        BusMux { data, sel }
+    }
+    pub fn set_data(&mut self, data: [[bool; N]; M]) {
+        self.data = data;
+    }
+    pub fn set_bus(&mut self, index: usize, bus: [bool; N]) {
+        self.data[index] = bus;
+    }
+    pub fn set_sel(&mut self, sel: [bool; K]) {
+        self.sel = sel;
+    }
+    pub fn data(&self) -> [[bool; N]; M] {
+        self.data
+    }
+    pub fn sel(&self) -> [bool; K] {
+        self.sel
+    }
+    pub fn output(&self) -> [bool; N] {
+        let mut output = [false; N];
+        (0..N).for_each(|i| {
+            let mut bits = [false; M];
+            (0..M).for_each(|j| {
+                bits[j] = self.data[j][i];
+            });
+            let mux = Mux::new(bits, self.sel);
+            output[i] = mux.output();
+        });
+        output
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -95,6 +238,10 @@ mod tests {
         assert!(mux.output());
         mux.set_sel(true);
         assert!(!mux.output());
+        mux.set_a(false);
+        mux.set_b(true);
+        assert_eq!((mux.a(), mux.b(), mux.sel()), (false, true, true));
+        assert!(mux.output());
     }
 
     #[test]
@@ -114,6 +261,10 @@ mod tests {
         mux.set_d(true);
         mux.set_sel((true, true));
         assert!(mux.output());
+        assert_eq!(
+            (mux.a(), mux.b(), mux.c(), mux.d(), mux.sel()),
+            (false, false, false, true, (true, true))
+        );
     }
 
     #[test]
@@ -126,5 +277,66 @@ mod tests {
         assert_eq!(demux.output(), (false, false, true, false));
         demux.set_sel((true, true));
         assert_eq!(demux.output(), (false, false, false, true));
+        assert_eq!(demux.sel(), (true, true));
+    }
+
+    #[test]
+    fn test_mux() {
+        let data = [false, true, true, false, true, false, false, false];
+        let mut mux = Mux::new(data, [false; 3]);
+        (0..8).for_each(|i| {
+            mux.set_sel([i & 1 == 1, i & 2 == 2, i & 4 == 4]);
+            assert_eq!(mux.output(), data[i]);
+        });
+        mux.set_data([true; 8]);
+        assert!(mux.output());
+        assert_eq!((mux.data(), mux.sel()), ([true; 8], [true; 3]));
+        let mux = Mux::new([true], []);
+        assert!(mux.output());
+    }
+
+    #[test]
+    fn test_demux() {
+        let mut demux = Demux::<2, 4>::new(true, [false, false]);
+        assert_eq!(demux.output(), [true, false, false, false]);
+        demux.set_sel([true, false]);
+        assert_eq!(demux.output(), [false, true, false, false]);
+        demux.set_sel([false, true]);
+        assert_eq!(demux.output(), [false, false, true, false]);
+        demux.set_sel([true, true]);
+        assert_eq!(demux.output(), [false, false, false, true]);
+        demux.set_a(false);
+        assert_eq!(demux.output(), [false; 4]);
+        assert_eq!((demux.a(), demux.sel()), (false, [true, true]));
+
+        let mut demux = Demux::<3, 8>::new(true, [false; 3]);
+        (0..8).for_each(|i| {
+            demux.set_sel([i & 1 == 1, i & 2 == 2, i & 4 == 4]);
+            let mut expected = [false; 8];
+            expected[i] = true;
+            assert_eq!(demux.output(), expected);
+        });
+    }
+
+    #[test]
+    fn test_bus_mux() {
+        let registers = [
+            [true, false, false, false],
+            [false, true, true, false],
+            [true, true, true, true],
+            [false, false, false, true],
+        ];
+        let mut mux = BusMux::new(registers, [false, false]);
+        assert_eq!(mux.output(), registers[0]);
+        mux.set_sel([true, false]);
+        assert_eq!(mux.output(), registers[1]);
+        mux.set_sel([false, true]);
+        assert_eq!(mux.output(), registers[2]);
+        mux.set_sel([true, true]);
+        assert_eq!(mux.output(), registers[3]);
+        mux.set_bus(3, [false; 4]);
+        assert_eq!(mux.output(), [false; 4]);
+        assert_eq!(mux.data()[3], [false; 4]);
+        assert_eq!(mux.sel(), [true, true]);
     }
 }
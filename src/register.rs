@@ -0,0 +1,170 @@
+use crate::{encoder::Decoder, flip_flop::ResettableDFlipFlop, multiplexer::BusMux};
+
+pub struct Register<const N: usize> {
+    dff: [ResettableDFlipFlop; N],
+}
+impl<const N: usize> Register<N> {
+    pub fn new() -> Self {
+        Self {
+            dff: std::array::from_fn(|_| ResettableDFlipFlop::new()),
+        }
+    }
+    pub fn set_d(&mut self, d: [bool; N]) {
+        self.dff.iter_mut().zip(d).for_each(|(dff, d)| dff.set_d(d));
+    }
+    pub fn set_clk(&mut self, clk: bool) {
+        self.dff.iter_mut().for_each(|dff| dff.set_clk(clk));
+    }
+    pub fn set_enable(&mut self, enable: bool) {
+        self.dff.iter_mut().for_each(|dff| dff.set_enable(enable));
+    }
+    pub fn set_reset(&mut self, reset: bool) {
+        self.dff.iter_mut().for_each(|dff| dff.set_clear(reset));
+    }
+    pub fn output(&self) -> [bool; N] {
+        let mut output = [false; N];
+        (0..N).for_each(|i| {
+            output[i] = self.dff[i].output();
+        });
+        output
+    }
+}
+impl<const N: usize> Default for Register<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+pub struct RegisterFile<const K: usize, const R: usize, const N: usize> {
+    registers: [Register<N>; R],
+    read_addr_a: [bool; K],
+    read_addr_b: [bool; K],
+    write_addr: [bool; K],
+    write_data: [bool; N],
+    write_enable: bool,
+}
+impl<const K: usize, const R: usize, const N: usize> RegisterFile<K, R, N> {
+    pub fn new() -> Self {
+        let mut register_file = Self {
+            registers: std::array::from_fn(|_| Register::new()),
+            read_addr_a: [false; K],
+            read_addr_b: [false; K],
+            write_addr: [false; K],
+            write_data: [false; N],
+            write_enable: false,
+        };
+        register_file.update();
+        register_file
+    }
+    pub fn set_read_addr_a(&mut self, addr: [bool; K]) {
+        self.read_addr_a = addr;
+    }
+    pub fn set_read_addr_b(&mut self, addr: [bool; K]) {
+        self.read_addr_b = addr;
+    }
+    pub fn set_write_addr(&mut self, addr: [bool; K]) {
+        self.write_addr = addr;
+        self.update();
+    }
+    pub fn set_write_data(&mut self, data: [bool; N]) {
+        self.write_data = data;
+        self.update();
+    }
+    pub fn set_write_enable(&mut self, enable: bool) {
+        self.write_enable = enable;
+        self.update();
+    }
+    pub fn set_clk(&mut self, clk: bool) {
+        self.registers.iter_mut().for_each(|r| r.set_clk(clk));
+    }
+    pub fn set_reset(&mut self, reset: bool) {
+        self.registers.iter_mut().for_each(|r| r.set_reset(reset));
+    }
+    pub fn register(&self, index: usize) -> [bool; N] {
+        self.registers[index].output()
+    }
+    pub fn output(&self) -> ([bool; N], [bool; N]) {
+        let values: [[bool; N]; R] = std::array::from_fn(|i| self.registers[i].output());
+        let port_a = BusMux::new(values, self.read_addr_a);
+        let port_b = BusMux::new(values, self.read_addr_b);
+        (port_a.output(), port_b.output())
+    }
+    fn update(&mut self) {
+        let decoder = Decoder::<K, R>::new(self.write_addr, self.write_enable);
+        self.registers
+            .iter_mut()
+            .zip(decoder.output())
+            .for_each(|(register, enable)| {
+                register.set_enable(enable);
+                register.set_d(self.write_data);
+            });
+    }
+}
+impl<const K: usize, const R: usize, const N: usize> Default for RegisterFile<K, R, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_register() {
+        let mut register = Register::<4>::new();
+        assert_eq!(register.output(), [false; 4]);
+        register.set_d([true, false, true, true]);
+        assert_eq!(register.output(), [false; 4]);
+        register.set_clk(true);
+        assert_eq!(register.output(), [true, false, true, true]);
+        register.set_clk(false);
+        register.set_enable(false);
+        register.set_d([false, true, false, false]);
+        register.set_clk(true);
+        assert_eq!(register.output(), [true, false, true, true]);
+        register.set_clk(false);
+        register.set_enable(true);
+        register.set_clk(true);
+        assert_eq!(register.output(), [false, true, false, false]);
+        register.set_reset(true);
+        assert_eq!(register.output(), [false; 4]);
+        register.set_reset(false);
+        register.set_clk(false);
+        register.set_clk(true);
+        assert_eq!(register.output(), [false, true, false, false]);
+    }
+
+    #[test]
+    fn test_register_file() {
+        let mut register_file = RegisterFile::<2, 4, 4>::new();
+        let values = [
+            [true, false, false, false],
+            [false, true, false, false],
+            [true, true, false, true],
+            [false, false, true, true],
+        ];
+        register_file.set_write_enable(true);
+        for (i, value) in values.iter().enumerate() {
+            register_file.set_write_addr([i & 1 == 1, i & 2 == 2]);
+            register_file.set_write_data(*value);
+            register_file.set_clk(true);
+            register_file.set_clk(false);
+        }
+        (0..4).for_each(|i| assert_eq!(register_file.register(i), values[i]));
+
+        register_file.set_read_addr_a([false, true]);
+        register_file.set_read_addr_b([true, true]);
+        assert_eq!(register_file.output(), (values[2], values[3]));
+
+        register_file.set_write_enable(false);
+        register_file.set_write_addr([false, true]);
+        register_file.set_write_data([false; 4]);
+        register_file.set_clk(true);
+        register_file.set_clk(false);
+        assert_eq!(register_file.output(), (values[2], values[3]));
+
+        register_file.set_reset(true);
+        assert_eq!(register_file.output(), ([false; 4], [false; 4]));
+    }
+}
@@ -0,0 +1,156 @@
+use crate::{flip_flop::ResettableDFlipFlop, multiplexer::OneBitMultiplexer};
+
+/// Universal shift register: serial in/out and parallel in/out, shifting towards the MSB
+/// (left) or the LSB (right). Loading takes priority over shifting.
+pub struct ShiftRegister<const N: usize> {
+    dff: [ResettableDFlipFlop; N],
+    data: [bool; N],
+    serial_in: bool,
+    right: bool,
+    load: bool,
+}
+impl<const N: usize> ShiftRegister<N> {
+    pub fn new() -> Self {
+        let mut shift_register = Self {
+            dff: std::array::from_fn(|_| ResettableDFlipFlop::new()),
+            data: [false; N],
+            serial_in: false,
+            right: false,
+            load: false,
+        };
+        shift_register.update();
+        shift_register
+    }
+    pub fn set_clk(&mut self, clk: bool) {
+        self.dff.iter_mut().for_each(|dff| dff.set_clk(clk));
+        self.update();
+    }
+    pub fn set_serial_in(&mut self, serial_in: bool) {
+        self.serial_in = serial_in;
+        self.update();
+    }
+    pub fn set_right(&mut self, right: bool) {
+        self.right = right;
+        self.update();
+    }
+    pub fn set_load(&mut self, load: bool) {
+        self.load = load;
+        self.update();
+    }
+    pub fn set_data(&mut self, data: [bool; N]) {
+        self.data = data;
+        self.update();
+    }
+    pub fn set_enable(&mut self, enable: bool) {
+        self.dff.iter_mut().for_each(|dff| dff.set_enable(enable));
+        self.update();
+    }
+    pub fn set_reset(&mut self, reset: bool) {
+        self.dff.iter_mut().for_each(|dff| dff.set_clear(reset));
+        self.update();
+    }
+    pub fn output(&self) -> [bool; N] {
+        std::array::from_fn(|i| self.dff[i].output())
+    }
+    pub fn serial_out(&self) -> bool {
+        match N {
+            0 => self.serial_in,
+            _ => {
+                let mux = OneBitMultiplexer::new(
+                    self.dff[N - 1].output(),
+                    self.dff[0].output(),
+                    self.right,
+                );
+                mux.output()
+            }
+        }
+    }
+    fn update(&mut self) {
+        let q = self.output();
+        (0..N).for_each(|i| {
+            let from_lower = if i == 0 { self.serial_in } else { q[i - 1] };
+            let from_upper = if i == N - 1 { self.serial_in } else { q[i + 1] };
+            let shift = OneBitMultiplexer::new(from_lower, from_upper, self.right);
+            let load = OneBitMultiplexer::new(shift.output(), self.data[i], self.load);
+            self.dff[i].set_d(load.output());
+        });
+    }
+}
+impl<const N: usize> Default for ShiftRegister<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn clock<const N: usize>(shift_register: &mut ShiftRegister<N>) {
+        shift_register.set_clk(true);
+        shift_register.set_clk(false);
+    }
+
+    #[test]
+    fn test_serial_in_serial_out() {
+        let mut shift_register = ShiftRegister::<3>::new();
+        let input = [true, false, true, true, false, false];
+        let mut output = vec![];
+        for bit in input {
+            shift_register.set_serial_in(bit);
+            clock(&mut shift_register);
+            output.push(shift_register.serial_out());
+        }
+        assert_eq!(output, [false, false, true, false, true, true]);
+    }
+
+    #[test]
+    fn test_serial_in_parallel_out() {
+        let mut shift_register = ShiftRegister::<4>::new();
+        shift_register.set_right(true);
+        for bit in [true, true, false, true] {
+            shift_register.set_serial_in(bit);
+            clock(&mut shift_register);
+        }
+        assert_eq!(shift_register.output(), [true, true, false, true]);
+    }
+
+    #[test]
+    fn test_parallel_in_serial_out() {
+        let mut shift_register = ShiftRegister::<4>::new();
+        shift_register.set_data([true, false, true, true]);
+        shift_register.set_load(true);
+        clock(&mut shift_register);
+        shift_register.set_load(false);
+        shift_register.set_right(true);
+        let mut output = vec![];
+        for _ in 0..4 {
+            output.push(shift_register.serial_out());
+            clock(&mut shift_register);
+        }
+        assert_eq!(output, [true, false, true, true]);
+        assert_eq!(shift_register.output(), [false; 4]);
+    }
+
+    #[test]
+    fn test_parallel_in_parallel_out() {
+        let mut shift_register = ShiftRegister::<4>::new();
+        shift_register.set_data([false, true, true, false]);
+        shift_register.set_load(true);
+        clock(&mut shift_register);
+        assert_eq!(shift_register.output(), [false, true, true, false]);
+        shift_register.set_load(false);
+        shift_register.set_enable(false);
+        clock(&mut shift_register);
+        assert_eq!(shift_register.output(), [false, true, true, false]);
+        shift_register.set_enable(true);
+        clock(&mut shift_register);
+        assert_eq!(shift_register.output(), [false, false, true, true]);
+        shift_register.set_right(true);
+        clock(&mut shift_register);
+        clock(&mut shift_register);
+        assert_eq!(shift_register.output(), [true, true, false, false]);
+        shift_register.set_reset(true);
+        assert_eq!(shift_register.output(), [false; 4]);
+    }
+}
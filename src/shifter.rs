@@ -0,0 +1,149 @@
+use crate::{gate::AndGate, multiplexer::OneBitMultiplexer};
+
+pub struct BarrelShifter<const N: usize, const S: usize> {
+    a: [bool; N],
+    shamt: [bool; S],
+    right: bool,
+    arithmetic: bool,
+    rotate: bool,
+}
+impl<const N: usize, const S: usize> BarrelShifter<N, S> {
+    pub fn new(
+        a: [bool; N],
+        shamt: [bool; S],
+        right: bool,
+        arithmetic: bool,
+        rotate: bool,
+    ) -> Self {
+        Self {
+            a,
+            shamt,
+            right,
+            arithmetic,
+            rotate,
+        }
+    }
+    pub fn set_a(&mut self, a: [bool; N]) {
+        self.a = a;
+    }
+    pub fn set_shamt(&mut self, shamt: [bool; S]) {
+        self.shamt = shamt;
+    }
+    pub fn set_right(&mut self, right: bool) {
+        self.right = right;
+    }
+    pub fn set_arithmetic(&mut self, arithmetic: bool) {
+        self.arithmetic = arithmetic;
+    }
+    pub fn set_rotate(&mut self, rotate: bool) {
+        self.rotate = rotate;
+    }
+    pub fn output(&self) -> [bool; N] {
+        // Right shifts are done by reversing the word, shifting left and reversing back.
+        let mut value = self.reverse(self.a);
+        let fill = match N {
+            0 => false,
+            _ => {
+                let and1 = AndGate::new(self.right, self.arithmetic);
+                let and2 = AndGate::new(and1.output(), self.a[N - 1]);
+                and2.output()
+            }
+        };
+        (0..S).for_each(|i| {
+            value = self.shift_layer(value, i, fill);
+        });
+        self.reverse(value)
+    }
+    fn reverse(&self, value: [bool; N]) -> [bool; N] {
+        let mut result = [false; N];
+        (0..N).for_each(|j| {
+            let mux = OneBitMultiplexer::new(value[j], value[N - 1 - j], self.right);
+            result[j] = mux.output();
+        });
+        result
+    }
+    fn shift_layer(&self, value: [bool; N], layer: usize, fill: bool) -> [bool; N] {
+        let amount = 1usize.checked_shl(layer as u32).unwrap_or(usize::MAX);
+        let mut result = [false; N];
+        (0..N).for_each(|j| {
+            let shifted = if j >= amount { value[j - amount] } else { fill };
+            let rotated = value[(j + N - amount % N) % N];
+            let source = OneBitMultiplexer::new(shifted, rotated, self.rotate);
+            let mux = OneBitMultiplexer::new(value[j], source.output(), self.shamt[layer]);
+            result[j] = mux.output();
+        });
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bits::{from_u64, to_u64};
+
+    #[test]
+    fn test_barrel_shifter() {
+        let mut shifter = BarrelShifter::new(
+            [true, false, true, true],
+            [true, false],
+            false,
+            false,
+            false,
+        );
+        assert_eq!(shifter.output(), [false, true, false, true]);
+        shifter.set_right(true);
+        assert_eq!(shifter.output(), [false, true, true, false]);
+        shifter.set_arithmetic(true);
+        assert_eq!(shifter.output(), [false, true, true, true]);
+        shifter.set_arithmetic(false);
+        shifter.set_rotate(true);
+        assert_eq!(shifter.output(), [false, true, true, true]);
+        shifter.set_right(false);
+        shifter.set_shamt([false, true]);
+        assert_eq!(shifter.output(), [true, true, true, false]);
+    }
+
+    #[test]
+    fn test_barrel_shifter_exhaustive() {
+        for a in 0..=255u8 {
+            for shamt in 0..8u32 {
+                let bits = from_u64::<8>(a as u64);
+                let amount = from_u64::<3>(shamt as u64);
+                let cases = [
+                    (false, false, false, a << shamt),
+                    (true, false, false, a >> shamt),
+                    (true, true, false, ((a as i8) >> shamt) as u8),
+                    (false, false, true, a.rotate_left(shamt)),
+                    (true, false, true, a.rotate_right(shamt)),
+                ];
+                for (right, arithmetic, rotate, expected) in cases {
+                    let shifter = BarrelShifter::new(bits, amount, right, arithmetic, rotate);
+                    assert_eq!(to_u64(&shifter.output()), expected as u64);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_barrel_shifter_overshift() {
+        let shifter = BarrelShifter::new(
+            [true, true, true],
+            [false, false, true],
+            false,
+            false,
+            false,
+        );
+        assert_eq!(shifter.output(), [false, false, false]);
+        let shifter =
+            BarrelShifter::new([true, false, true], [false, false, true], true, true, false);
+        assert_eq!(shifter.output(), [true, true, true]);
+        let shifter = BarrelShifter::new(
+            [true, false, false],
+            [false, false, true],
+            false,
+            false,
+            true,
+        );
+        assert_eq!(shifter.output(), [false, true, false]);
+    }
+}
@@ -1,4 +1,7 @@
-use crate::{adder::FullAdder, gate::NotGate};
+use crate::{
+    adder::FullAdder,
+    gate::{AndGate, NotGate, XorGate},
+};
 
 pub struct NBitFullSubtractor<const N: usize> {
     a: [bool; N],
@@ -22,6 +25,71 @@ impl<const N: usize> NBitFullSubtractor<N> {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AddSubMode {
+    Add,
+    Sub,
+    Increment,
+    Decrement,
+    Negate,
+}
+impl AddSubMode {
+    // (zero_a, zero_b, invert_b, carry_in)
+    fn controls(self) -> (bool, bool, bool, bool) {
+        match self {
+            AddSubMode::Add => (false, false, false, false),
+            AddSubMode::Sub => (false, false, true, true),
+            AddSubMode::Increment => (false, true, false, true),
+            AddSubMode::Decrement => (false, true, true, false),
+            AddSubMode::Negate => (true, false, true, true),
+        }
+    }
+}
+
+pub struct NBitAdderSubtractor<const N: usize> {
+    a: [bool; N],
+    b: [bool; N],
+    mode: AddSubMode,
+}
+impl<const N: usize> NBitAdderSubtractor<N> {
+    pub fn new(a: [bool; N], b: [bool; N], mode: AddSubMode) -> Self {
+        Self { a, b, mode }
+    }
+    pub fn set_a(&mut self, a: [bool; N]) {
+        self.a = a;
+    }
+    pub fn set_b(&mut self, b: [bool; N]) {
+        self.b = b;
+    }
+    pub fn set_mode(&mut self, mode: AddSubMode) {
+        self.mode = mode;
+    }
+    pub fn set_sub(&mut self, sub: bool) {
+        self.mode = if sub {
+            AddSubMode::Sub
+        } else {
+            AddSubMode::Add
+        };
+    }
+    pub fn output(&self) -> ([bool; N], bool) {
+        let (zero_a, zero_b, invert_b, carry_in) = self.mode.controls();
+        let keep_a = NotGate::new(zero_a);
+        let keep_b = NotGate::new(zero_b);
+        let mut carry = carry_in;
+        let mut result = [false; N];
+        (0..N).for_each(|i| {
+            let a = AndGate::new(self.a[i], keep_a.output());
+            let b = AndGate::new(self.b[i], keep_b.output());
+            let xor = XorGate::new(b.output(), invert_b);
+            let full_adder = FullAdder::new(a.output(), xor.output(), carry);
+            let full_adder_output = full_adder.output();
+            result[i] = full_adder_output.0;
+            carry = full_adder_output.1;
+        });
+        (result, carry)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -32,4 +100,23 @@ mod tests {
             NBitFullSubtractor::new([true, false, true], [true, true, false]);
         assert_eq!(n_bit_full_subtractor.output(), ([false, true, false], true));
     }
+
+    #[test]
+    fn test_n_bit_adder_subtractor() {
+        let mut adder_subtractor =
+            NBitAdderSubtractor::new([true, false, true], [true, true, false], AddSubMode::Add);
+        assert_eq!(adder_subtractor.output(), ([false, false, false], true));
+        adder_subtractor.set_sub(true);
+        assert_eq!(adder_subtractor.output(), ([false, true, false], true));
+        adder_subtractor.set_mode(AddSubMode::Increment);
+        assert_eq!(adder_subtractor.output(), ([false, true, true], false));
+        adder_subtractor.set_mode(AddSubMode::Decrement);
+        assert_eq!(adder_subtractor.output(), ([false, false, true], true));
+        adder_subtractor.set_mode(AddSubMode::Negate);
+        assert_eq!(adder_subtractor.output(), ([true, false, true], false));
+        adder_subtractor.set_a([true, true, true]);
+        assert_eq!(adder_subtractor.output(), ([true, false, true], false));
+        adder_subtractor.set_mode(AddSubMode::Increment);
+        assert_eq!(adder_subtractor.output(), ([false, false, false], true));
+    }
 }
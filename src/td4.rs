@@ -0,0 +1,252 @@
+use crate::{
+    adder::NBitFullAdder,
+    bits::from_u64,
+    counter::SynchronousCounter,
+    cpu::Cpu,
+    flip_flop::ResettableDFlipFlop,
+    gate::{AndGate, NotGate, OrGate},
+    memory::{GateRom, Rom},
+    multiplexer::BusMux,
+    register::Register,
+};
+
+/// Each instruction is one byte: the opcode in the high nibble and an immediate in the low
+/// nibble. Every instruction adds the immediate to the selected source, so MOV A, B is
+/// encoded as B + 0 and MOV A, Im as 0 + Im.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Td4Instruction {
+    AddA(u8),
+    MovAB,
+    InA,
+    MovA(u8),
+    MovBA,
+    AddB(u8),
+    InB,
+    MovB(u8),
+    OutB,
+    OutIm(u8),
+    Jnc(u8),
+    Jmp(u8),
+}
+impl Td4Instruction {
+    pub fn encode(self) -> u8 {
+        let (opcode, im) = match self {
+            Td4Instruction::AddA(im) => (0b0000, im),
+            Td4Instruction::MovAB => (0b0001, 0),
+            Td4Instruction::InA => (0b0010, 0),
+            Td4Instruction::MovA(im) => (0b0011, im),
+            Td4Instruction::MovBA => (0b0100, 0),
+            Td4Instruction::AddB(im) => (0b0101, im),
+            Td4Instruction::InB => (0b0110, 0),
+            Td4Instruction::MovB(im) => (0b0111, im),
+            Td4Instruction::OutB => (0b1001, 0),
+            Td4Instruction::OutIm(im) => (0b1011, im),
+            Td4Instruction::Jnc(im) => (0b1110, im),
+            Td4Instruction::Jmp(im) => (0b1111, im),
+        };
+        (opcode << 4) | (im & 0x0f)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Td4State {
+    pub a: [bool; 4],
+    pub b: [bool; 4],
+    pub out: [bool; 4],
+    pub pc: [bool; 4],
+    pub carry: bool,
+}
+
+pub struct Td4 {
+    rom: GateRom<4, 16, 8>,
+    a: Register<4>,
+    b: Register<4>,
+    out: Register<4>,
+    pc: SynchronousCounter<4>,
+    carry: ResettableDFlipFlop,
+    input: [bool; 4],
+}
+impl Td4 {
+    pub fn new(program: [u8; 16]) -> Td4 {
+        let words = program.map(|byte| from_u64(byte as u64)).to_vec();
+        let mut td4 = Td4 {
+            rom: GateRom::from_words(words),
+            a: Register::new(),
+            b: Register::new(),
+            out: Register::new(),
+            pc: SynchronousCounter::new(),
+            carry: ResettableDFlipFlop::new(),
+            input: [false; 4],
+        };
+        td4.update();
+        td4
+    }
+    pub fn set_input(&mut self, input: [bool; 4]) {
+        self.input = input;
+        self.update();
+    }
+    pub fn output(&self) -> [bool; 4] {
+        self.out.output()
+    }
+    fn update(&mut self) {
+        self.rom.set_addr(self.pc.output());
+        let word = self.rom.output();
+        let im = [word[0], word[1], word[2], word[3]];
+        let op = [word[4], word[5], word[6], word[7]];
+
+        // The source selector picks A, B, the input port or zero.
+        let select_a = OrGate::new(op[0], op[3]);
+        let source = BusMux::<2, 4, 4>::new(
+            [self.a.output(), self.b.output(), self.input, [false; 4]],
+            [select_a.output(), op[1]],
+        );
+        let (sum, carry) = NBitFullAdder::new(source.output(), im).output();
+
+        let not_op2 = NotGate::new(op[2]);
+        let not_op3 = NotGate::new(op[3]);
+        let load_a = AndGate::new(not_op3.output(), not_op2.output());
+        let load_b = AndGate::new(not_op3.output(), op[2]);
+        let load_out = AndGate::new(op[3], not_op2.output());
+        // JMP always loads the PC; JNC only when the previous instruction left no carry.
+        let not_carry = NotGate::new(self.carry.output());
+        let taken = OrGate::new(op[0], not_carry.output());
+        let jump = AndGate::new(op[3], op[2]);
+        let load_pc = AndGate::new(jump.output(), taken.output());
+
+        for (register, load) in [
+            (&mut self.a, load_a.output()),
+            (&mut self.b, load_b.output()),
+            (&mut self.out, load_out.output()),
+        ] {
+            register.set_enable(load);
+            register.set_d(sum);
+        }
+        self.carry.set_d(carry);
+        self.pc.set_data(sum);
+        self.pc.set_load(load_pc.output());
+    }
+}
+impl Cpu for Td4 {
+    type State = Td4State;
+    fn reset(&mut self) {
+        for register in [&mut self.a, &mut self.b, &mut self.out] {
+            register.set_reset(true);
+            register.set_reset(false);
+        }
+        self.pc.set_reset(true);
+        self.pc.set_reset(false);
+        self.carry.set_clear(true);
+        self.carry.set_clear(false);
+        self.update();
+    }
+    fn tick(&mut self, clk: bool) {
+        self.a.set_clk(clk);
+        self.b.set_clk(clk);
+        self.out.set_clk(clk);
+        self.pc.set_clk(clk);
+        self.carry.set_clk(clk);
+        self.update();
+    }
+    fn state(&self) -> Td4State {
+        Td4State {
+            a: self.a.output(),
+            b: self.b.output(),
+            out: self.out.output(),
+            pc: self.pc.output(),
+            carry: self.carry.output(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bits::to_u64;
+
+    fn assemble(instructions: &[Td4Instruction]) -> [u8; 16] {
+        let mut program = [0; 16];
+        program
+            .iter_mut()
+            .zip(instructions)
+            .for_each(|(byte, instruction)| *byte = instruction.encode());
+        program
+    }
+
+    fn registers(td4: &Td4) -> (u64, u64, u64, u64, bool) {
+        let state = td4.state();
+        (
+            to_u64(&state.a),
+            to_u64(&state.b),
+            to_u64(&state.out),
+            to_u64(&state.pc),
+            state.carry,
+        )
+    }
+
+    #[test]
+    fn test_td4_instructions() {
+        use Td4Instruction::*;
+        let program = [
+            InA,
+            MovBA,
+            AddA(7),
+            MovA(3),
+            MovAB,
+            AddB(9),
+            InB,
+            MovB(2),
+            OutB,
+            OutIm(5),
+        ];
+        let mut td4 = Td4::new(assemble(&program));
+        td4.reset();
+        td4.set_input(from_u64(10));
+        let mut states = vec![];
+        for _ in 0..program.len() {
+            td4.step();
+            states.push(registers(&td4));
+        }
+        assert_eq!(
+            states,
+            [
+                (10, 0, 0, 1, false),
+                (10, 10, 0, 2, false),
+                (1, 10, 0, 3, true),
+                (3, 10, 0, 4, false),
+                (10, 10, 0, 5, false),
+                (10, 3, 0, 6, true),
+                (10, 10, 0, 7, false),
+                (10, 2, 0, 8, false),
+                (10, 2, 2, 9, false),
+                (10, 2, 5, 10, false),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_td4_program() {
+        use Td4Instruction::*;
+        // Counts B up on the output port until it wraps, then halts.
+        let program = [OutB, AddB(1), Jnc(0), OutIm(9), Jmp(4)];
+        let mut td4 = Td4::new(assemble(&program));
+        td4.reset();
+        let mut outputs = vec![];
+        for _ in 0..16 * 3 + 3 {
+            td4.step();
+            let out = to_u64(&td4.output());
+            if outputs.last() != Some(&out) {
+                outputs.push(out);
+            }
+        }
+        assert_eq!(outputs, (0..16).chain([9]).collect::<Vec<_>>());
+        assert_eq!(to_u64(&td4.state().pc), 4);
+        assert_eq!(to_u64(&td4.state().b), 0);
+
+        // JNC falls through only straight after an ADD that carried.
+        let program = [MovA(14), AddA(1), Jnc(1), OutIm(1), Jmp(4)];
+        let mut td4 = Td4::new(assemble(&program));
+        td4.reset();
+        (0..7).for_each(|_| td4.step());
+        assert_eq!(registers(&td4), (0, 0, 1, 4, false));
+    }
+}